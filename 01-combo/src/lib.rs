@@ -0,0 +1,778 @@
+use std::fmt;
+
+use common::{Answer, Rng, Solution};
+use eyre::{Result, eyre};
+use nom::branch::alt;
+use nom::bytes::complete::take_till1;
+use nom::character::complete::{char, digit1, one_of};
+use nom::combinator::{all_consuming, map, map_res, opt, recognize, verify};
+use nom::sequence::{delimited, preceded};
+use nom::{IResult, Parser};
+
+/// What an instruction does to the dial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    /// Turns the dial this many clicks towards lower numbers.
+    Left(u128),
+    /// Turns the dial this many clicks towards higher numbers.
+    Right(u128),
+    /// Puts the dial straight onto a position, counting back from the top if
+    /// negative, without passing or landing on anything.
+    Set(isize),
+}
+
+impl Move {
+    /// Clicks the move turns the dial through.
+    pub fn clicks(self) -> u128 {
+        match self {
+            Self::Left(clicks) | Self::Right(clicks) => clicks,
+            Self::Set(_) => 0,
+        }
+    }
+}
+
+impl fmt::Display for Move {
+    /// Formats the move the way [`parse_line`] reads it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Left(clicks) => write!(f, "L{clicks}"),
+            Self::Right(clicks) => write!(f, "R{clicks}"),
+            Self::Set(position) => write!(f, "={position}"),
+        }
+    }
+}
+
+/// A move and the line it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    /// 1-based line number.
+    pub line: usize,
+    pub action: Move,
+    /// How many times in a row to make the move.
+    pub times: usize,
+}
+
+/// The instructions of a rotation list and the labelled sections they fall in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rotations<'a> {
+    pub instructions: Vec<Instruction>,
+    /// Each section's label and the line it starts on; a section runs until the next
+    /// one starts.
+    pub sections: Vec<(&'a str, usize)>,
+}
+
+/// One line of a rotation list, once its comment is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line<'a> {
+    /// `[label]`, starting a section.
+    Label(&'a str),
+    /// A move, and the times to repeat it.
+    Instruction(Move, usize),
+}
+
+/// Most times one line may repeat its move, so a short input cannot ask for
+/// endless work.
+pub const MAX_REPEATS: usize = 1000;
+
+fn parse_clicks(input: &str) -> IResult<&str, u128> {
+    map_res(digit1, str::parse).parse(input)
+}
+
+fn parse_times(input: &str) -> IResult<&str, usize> {
+    let times = map_res(digit1, str::parse);
+    let times = verify(times, |t| (1..=MAX_REPEATS).contains(t));
+    map(opt(preceded(char('*'), times)), |t| t.unwrap_or(1)).parse(input)
+}
+
+fn parse_turn(input: &str) -> IResult<&str, Move> {
+    map((one_of("LR"), parse_clicks), |(dir, clicks)| match dir {
+        'L' => Move::Left(clicks),
+        'R' => Move::Right(clicks),
+        _ => unreachable!(),
+    })
+    .parse(input)
+}
+
+fn parse_set(input: &str) -> IResult<&str, Move> {
+    let position = map_res(recognize((opt(one_of("+-")), digit1)), str::parse);
+    map(preceded(char('='), position), Move::Set).parse(input)
+}
+
+/// Parses `L<clicks>` or `R<clicks>`, or `=<position>`, either followed by an
+/// optional `*<times>`; or `[label]`.
+pub fn parse_line(input: &str) -> IResult<&str, Line<'_>> {
+    let label = delimited(char('['), take_till1(|c| c == ']'), char(']'));
+    all_consuming(alt((
+        map(label, |label: &str| Line::Label(label.trim())),
+        map((alt((parse_turn, parse_set)), parse_times), |(action, times)| {
+            Line::Instruction(action, times)
+        }),
+    )))
+    .parse(input)
+}
+
+/// Parses one instruction per line, skipping blank lines and `#` comments.
+pub fn parse<'a>(fname: &str, body: &'a str) -> Result<Rotations<'a>> {
+    let mut rotations = Rotations { instructions: Vec::new(), sections: Vec::new() };
+    for (i, line) in body.lines().enumerate() {
+        let code = line.split_once('#').map_or(line, |(code, _)| code).trim();
+        if code.is_empty() {
+            continue;
+        }
+        match common::finish(fname, body, parse_line(code))? {
+            Line::Label(label) => rotations.sections.push((label, i + 1)),
+            Line::Instruction(action, times) => {
+                rotations.instructions.push(Instruction { line: i + 1, action, times })
+            }
+        }
+    }
+    Ok(rotations)
+}
+
+/// A dial numbered from 0 up to one less than its size, turned right towards higher
+/// numbers and left towards lower ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    size: isize,
+    position: isize,
+}
+
+impl Default for Dial {
+    /// The puzzle's dial: 100 positions, starting at 50.
+    fn default() -> Self {
+        Self { size: 100, position: 50 }
+    }
+}
+
+impl Dial {
+    /// Fails unless `start` is one of the dial's positions.
+    pub fn new(size: isize, start: isize) -> Result<Self> {
+        if size <= 0 {
+            return Err(eyre!("a dial needs at least one position, not {size}"));
+        }
+        if !(0..size).contains(&start) {
+            return Err(eyre!("{start} is not a position on a dial of size {size}"));
+        }
+        Ok(Self { size, position: start })
+    }
+
+    pub fn size(&self) -> isize {
+        self.size
+    }
+
+    pub fn position(&self) -> isize {
+        self.position
+    }
+
+    /// Puts the dial straight onto `position`, taken round the dial, and returns
+    /// where that is.
+    pub fn set(&mut self, position: isize) -> isize {
+        self.position = position.rem_euclid(self.size);
+        self.position
+    }
+
+    /// Makes `action`, and returns where the dial lands and the times it passed zero
+    /// on the way, not counting landing there.
+    ///
+    /// The clicks are never added to the position, so any number of them can be
+    /// counted: zero comes round once per full turn, and once more in the part turn
+    /// left over if that reaches it.
+    pub fn rotate(&mut self, action: Move) -> (isize, u128) {
+        let size = self.size as u128;
+        let from = self.position as u128;
+        let (to, hits) = match action {
+            Move::Right(clicks) => {
+                let turned = from + clicks % size;
+                (turned % size, clicks / size + turned / size)
+            }
+            Move::Left(clicks) => {
+                // Zero is `from` clicks away, or a full turn away when starting on it.
+                let rest = clicks % size;
+                (
+                    (from + size - rest) % size,
+                    clicks / size + u128::from(from != 0 && rest >= from),
+                )
+            }
+            Move::Set(position) => return (self.set(position), 0),
+        };
+        self.position = to as isize;
+        let landed = to == 0 && action.clicks() != 0;
+        (self.position, hits - u128::from(landed))
+    }
+
+    /// [`Dial::rotate`] one click at a time, returning where the dial lands and the
+    /// clicks that left it on zero, the last one included.
+    pub fn click(&mut self, action: Move) -> (isize, u128) {
+        let step = match action {
+            Move::Left(_) => -1,
+            Move::Right(_) => 1,
+            Move::Set(position) => return (self.set(position), 0),
+        };
+        let mut zeros = 0;
+        for _ in 0..action.clicks() {
+            self.position = (self.position + step).rem_euclid(self.size);
+            if self.position == 0 {
+                zeros += 1;
+            }
+        }
+        (self.position, zeros)
+    }
+}
+
+/// What one move did to the dial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn {
+    /// The line of the instruction making the move.
+    pub line: usize,
+    pub action: Move,
+    pub from: isize,
+    pub to: isize,
+    /// 1 if the dial was turned onto zero.
+    pub zeros1: u128,
+    /// The clicks that left the dial on zero, passing it or landing there.
+    pub zeros2: u128,
+}
+
+impl fmt::Display for Turn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {}, zeros1 +{}, zeros2 +{}",
+            self.action, self.from, self.to, self.zeros1, self.zeros2
+        )
+    }
+}
+
+/// Makes each of `instructions`' moves in order.
+pub fn turns(
+    mut dial: Dial,
+    instructions: &[Instruction],
+) -> impl Iterator<Item = Turn> {
+    let moves = instructions.iter().flat_map(|i| std::iter::repeat_n(i, i.times));
+    moves.map(move |&Instruction { line, action, .. }| {
+        let from = dial.position();
+        let (to, passes) = dial.rotate(action);
+        let zeros1 = u128::from(to == 0 && !matches!(action, Move::Set(_)));
+        // Staying put on zero is a landing, but no click pointed the dial there.
+        let zeros2 = passes + u128::from(to == 0 && action.clicks() != 0);
+        Turn { line, action, from, to, zeros1, zeros2 }
+    })
+}
+
+/// A move that [`turns`] gets wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+    /// What [`turns`] says the move did.
+    pub fast: Turn,
+    /// What turning the dial one click at a time did.
+    pub slow: Turn,
+}
+
+/// Checks [`turns`] against [`Dial::click`], returning the first move they
+/// disagree on, or `None` if clicking through the moves would take more than
+/// [`MAX_REFERENCE_CLICKS`] clicks.
+pub fn verify_turns(
+    dial: Dial,
+    instructions: &[Instruction],
+) -> Option<Option<Divergence>> {
+    if total_clicks(instructions)? > MAX_REFERENCE_CLICKS {
+        return None;
+    }
+    let mut clicked = dial;
+    for fast in turns(dial, instructions) {
+        let from = clicked.position();
+        let (to, zeros2) = clicked.click(fast.action);
+        let zeros1 = u128::from(to == 0 && !matches!(fast.action, Move::Set(_)));
+        let slow = Turn { from, to, zeros1, zeros2, ..fast };
+        if fast != slow {
+            return Some(Some(Divergence { fast, slow }));
+        }
+    }
+    Some(None)
+}
+
+/// Returns the times the dial lands on zero and the times it passes or lands on
+/// zero.
+///
+/// Fails if the counts do not fit in a `u128`.
+pub fn count_zeros(instructions: &[Instruction]) -> Result<(u128, u128)> {
+    count_zeros_from(Dial::default(), instructions)
+}
+
+/// [`count_zeros`] for any dial.
+pub fn count_zeros_from(
+    dial: Dial,
+    instructions: &[Instruction],
+) -> Result<(u128, u128)> {
+    let mut zeros1 = 0;
+    let mut zeros2 = 0u128;
+    for turn in turns(dial, instructions) {
+        zeros1 += turn.zeros1;
+        zeros2 = zeros2.checked_add(turn.zeros2).ok_or_else(too_many_zeros)?;
+    }
+    Ok((zeros1, zeros2))
+}
+
+fn too_many_zeros() -> eyre::Report {
+    eyre!("too many zeros to count")
+}
+
+/// [`count_zeros_from`] within each labelled section, with the dial carrying on
+/// from the section before.
+pub fn subtotals<'a>(
+    dial: Dial,
+    rotations: &Rotations<'a>,
+) -> Result<Vec<(&'a str, u128, u128)>> {
+    let sections = &rotations.sections;
+    let mut totals =
+        sections.iter().map(|&(label, _)| (label, 0, 0u128)).collect::<Vec<_>>();
+    for turn in turns(dial, &rotations.instructions) {
+        let started = sections.partition_point(|&(_, line)| line < turn.line);
+        if let Some(total) = started.checked_sub(1).map(|i| &mut totals[i]) {
+            total.1 += turn.zeros1;
+            total.2 = total.2.checked_add(turn.zeros2).ok_or_else(too_many_zeros)?;
+        }
+    }
+    Ok(totals)
+}
+
+/// Most positions [`sweep_starts`] will keep counts for.
+pub const MAX_SWEEP_SIZE: usize = 1_000_000;
+
+/// [`count_zeros_from`] every start on a dial of `size` positions, indexed by the
+/// start, in one pass over `rotations`. It keeps a few counts per position, so it
+/// fails for dials of more than [`MAX_SWEEP_SIZE`] positions.
+///
+/// Every start sees the same turns, shifted round the dial, so each rotation only
+/// adds its full spins to all of them plus one more zero to a run of starts. Those
+/// runs are kept as differences and summed at the end. Once a move sets the dial,
+/// every start is in the same place and counts the same from there on.
+pub fn sweep_starts(
+    size: isize,
+    instructions: &[Instruction],
+) -> Result<Vec<(u128, u128)>> {
+    let n = usize::try_from(size).ok().filter(|&n| n > 0);
+    let n = n.ok_or_else(|| eyre!("a dial needs at least one position, not {size}"))?;
+    if n > MAX_SWEEP_SIZE {
+        return Err(eyre!("can only sweep dials of up to {MAX_SWEEP_SIZE} positions"));
+    }
+    // Where every start has moved to, relative to where it started.
+    let mut offset = 0isize;
+    let mut spins = 0u128;
+    let mut landings = vec![0u128; n];
+    let mut passes = vec![0isize; n + 1];
+    let mut after_set = (0, 0);
+    'sweep: for (i, instruction) in instructions.iter().enumerate() {
+        for _ in 0..instruction.times {
+            let clicks = match instruction.action {
+                Move::Left(clicks) | Move::Right(clicks) => clicks,
+                Move::Set(position) => {
+                    let mut dial = Dial::new(size, 0)?;
+                    dial.set(position);
+                    after_set = count_zeros_from(dial, &instructions[i + 1..])?;
+                    break 'sweep;
+                }
+            };
+            let right = matches!(instruction.action, Move::Right(_));
+            spins = spins.checked_add(clicks / n as u128).ok_or_else(too_many_zeros)?;
+            let rest = (clicks % n as u128) as isize;
+            // The positions that reach zero in the part of the turn after the full
+            // spins: going right the last `rest`, going left 1 to `rest`, as zero
+            // itself would need a full spin.
+            let first = if right { size - rest } else { 1 };
+            // The starts now at those positions.
+            let start = (first - offset).rem_euclid(size) as usize;
+            let end = start + rest as usize;
+            passes[start] += 1;
+            passes[end.min(n)] -= 1;
+            if end > n {
+                passes[0] += 1;
+                passes[end - n] -= 1;
+            }
+            offset = (offset + if right { rest } else { size - rest }) % size;
+            landings[(-offset).rem_euclid(size) as usize] += 1;
+        }
+    }
+    let mut zeros = Vec::with_capacity(n);
+    let mut extra = 0;
+    for (start, &landed) in landings.iter().enumerate() {
+        extra += passes[start];
+        let passed = spins.checked_add(extra as u128 + after_set.1);
+        zeros.push((landed + after_set.0, passed.ok_or_else(too_many_zeros)?));
+    }
+    Ok(zeros)
+}
+
+/// Most clicks [`count_zeros_by_clicks`] will turn the dial through.
+pub const MAX_REFERENCE_CLICKS: u128 = 1_000_000;
+
+/// Clicks `instructions` turn the dial through, or `None` if there are more than
+/// fit in a `u128`.
+fn total_clicks(instructions: &[Instruction]) -> Option<u128> {
+    instructions.iter().try_fold(0u128, |total, i| {
+        total.checked_add(i.action.clicks().checked_mul(i.times as u128)?)
+    })
+}
+
+/// [`count_zeros_from`] by turning the dial one click at a time, or `None` if that
+/// would take more than [`MAX_REFERENCE_CLICKS`] clicks.
+pub fn count_zeros_by_clicks(
+    mut dial: Dial,
+    instructions: &[Instruction],
+) -> Option<(u128, u128)> {
+    if total_clicks(instructions)? > MAX_REFERENCE_CLICKS {
+        return None;
+    }
+    let mut zeros1 = 0;
+    let mut zeros2 = 0;
+    for instruction in instructions {
+        for _ in 0..instruction.times {
+            let (position, zeros) = dial.click(instruction.action);
+            let turned = !matches!(instruction.action, Move::Set(_));
+            zeros1 += u128::from(position == 0 && turned);
+            zeros2 += zeros;
+        }
+    }
+    Some((zeros1, zeros2))
+}
+
+fn answer(zeros: u128) -> Result<Answer> {
+    let zeros = i128::try_from(zeros).map_err(|_| eyre!("too many zeros to report"))?;
+    Ok(Answer::Int(zeros))
+}
+
+/// What [`plan`] keeps as low as it can.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    Clicks,
+    /// The clicks that leave the dial on zero.
+    Zeros,
+}
+
+/// The moves that turn `dial` onto each of `targets` in order, one move per target
+/// except those the dial is already on.
+///
+/// Each move goes whichever way round costs less by `goal`, with the other measure,
+/// then turning right, breaking ties. Every move ends on a fixed target, so
+/// choosing each one on its own gives the best plan overall.
+pub fn plan(mut dial: Dial, targets: &[isize], goal: Goal) -> Result<Vec<Move>> {
+    let size = dial.size();
+    let mut moves = Vec::with_capacity(targets.len());
+    for &target in targets {
+        if !(0..size).contains(&target) {
+            return Err(eyre!("{target} is not a position on a dial of size {size}"));
+        }
+        let position = dial.position();
+        if target == position {
+            continue;
+        }
+        let right = Move::Right((target - position).rem_euclid(size) as u128);
+        let left = Move::Left((position - target).rem_euclid(size) as u128);
+        let cost = |action: Move| {
+            let mut trial = dial;
+            let (to, passes) = trial.rotate(action);
+            let zeros = passes + u128::from(to == 0 && action.clicks() != 0);
+            match goal {
+                Goal::Clicks => (action.clicks(), zeros),
+                Goal::Zeros => (zeros, action.clicks()),
+            }
+        };
+        let action = if cost(left) < cost(right) { left } else { right };
+        dial.rotate(action);
+        moves.push(action);
+    }
+    Ok(moves)
+}
+
+pub fn part1(instructions: &[Instruction]) -> Result<u128> {
+    Ok(count_zeros(instructions)?.0)
+}
+
+pub fn part2(instructions: &[Instruction]) -> Result<u128> {
+    Ok(count_zeros(instructions)?.1)
+}
+
+/// `size` rotations of up to 999 clicks each.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}{}\n", rng.pick(&['L', 'R']), rng.range(1..=999)))
+        .collect()
+}
+
+pub struct Combo;
+
+impl Solution for Combo {
+    type Input<'a> = Rotations<'a>;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(rotations: &Self::Input<'_>) -> Result<Answer> {
+        answer(part1(&rotations.instructions)?)
+    }
+
+    fn part2(rotations: &Self::Input<'_>) -> Result<Answer> {
+        answer(part2(&rotations.instructions)?)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part1(rotations: &Self::Input<'_>) -> Option<Result<Answer>> {
+        count_zeros_by_clicks(Dial::default(), &rotations.instructions)
+            .map(|(zeros, _)| answer(zeros))
+    }
+
+    fn reference_part2(rotations: &Self::Input<'_>) -> Option<Result<Answer>> {
+        count_zeros_by_clicks(Dial::default(), &rotations.instructions)
+            .map(|(_, zeros)| answer(zeros))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day01-example.txt");
+
+    fn turns_of(clicks: &[isize]) -> Vec<Instruction> {
+        let turn = |(i, &clicks): (usize, &isize)| Instruction {
+            line: i + 1,
+            action: if clicks < 0 {
+                Move::Left(clicks.unsigned_abs() as u128)
+            } else {
+                Move::Right(clicks as u128)
+            },
+            times: 1,
+        };
+        clicks.iter().enumerate().map(turn).collect()
+    }
+
+    #[test]
+    fn example() {
+        let rotations = parse("example", EXAMPLE).unwrap();
+        assert_eq!(part1(&rotations.instructions).unwrap(), 3);
+        assert_eq!(part2(&rotations.instructions).unwrap(), 6);
+    }
+
+    #[test]
+    fn left_from_zero_is_not_a_pass() {
+        assert_eq!(count_zeros(&turns_of(&[-50, -5])).unwrap(), (1, 1));
+        assert_eq!(count_zeros(&turns_of(&[-50, -100])).unwrap(), (2, 2));
+        assert_eq!(count_zeros(&turns_of(&[-50, -105])).unwrap(), (1, 2));
+    }
+
+    #[test]
+    fn right_onto_zero_is_not_a_pass() {
+        assert_eq!(count_zeros(&turns_of(&[50])).unwrap(), (1, 1));
+        assert_eq!(count_zeros(&turns_of(&[150])).unwrap(), (1, 2));
+        assert_eq!(count_zeros(&turns_of(&[-50, 100])).unwrap(), (2, 2));
+    }
+
+    #[test]
+    fn no_clicks_on_zero() {
+        assert_eq!(count_zeros(&turns_of(&[-50, 0])).unwrap(), (2, 1));
+        assert_eq!(
+            count_zeros_by_clicks(Dial::default(), &turns_of(&[-50, 0])),
+            Some((2, 1))
+        );
+    }
+
+    #[test]
+    fn huge_turns() {
+        let huge = |body: String| parse("huge", &body).unwrap().instructions;
+        let full_turns = u128::MAX / 100;
+        // 50 + 55 is past zero, and 5 - 55 is too.
+        let rotations = huge(format!("R{}\nL{}\n", u128::MAX, u128::MAX));
+        assert_eq!(count_zeros(&rotations).unwrap(), (0, 2 * full_turns + 2));
+        assert!(parse("huge", &format!("R{}0\n", u128::MAX)).is_err());
+
+        let one = Dial::new(1, 0).unwrap();
+        let rotations = huge(format!("R{}\nR1\n", u128::MAX));
+        let e = count_zeros_from(one, &rotations).unwrap_err();
+        assert_eq!(e.to_string(), "too many zeros to count");
+        // Countable, but past the largest answer.
+        let body = format!("R{}*60\n", u128::MAX);
+        assert!(Combo::part2(&parse("huge", &body).unwrap()).is_err());
+        // Too many clicks to check one at a time.
+        assert_eq!(verify_turns(Dial::default(), &rotations), None);
+    }
+
+    #[test]
+    fn clicks_match_example() {
+        let rotations = parse("example", EXAMPLE).unwrap().instructions;
+        assert_eq!(count_zeros_by_clicks(Dial::default(), &rotations), Some((3, 6)));
+        assert_eq!(
+            count_zeros_by_clicks(Dial::default(), &turns_of(&[isize::MAX])),
+            None
+        );
+    }
+
+    #[test]
+    fn full_turns() {
+        assert_eq!(count_zeros(&turns_of(&[1000])).unwrap(), (0, 10));
+        assert_eq!(count_zeros(&turns_of(&[-1000])).unwrap(), (0, 10));
+        assert_eq!(count_zeros(&turns_of(&[-150])).unwrap(), (1, 2));
+        assert_eq!(count_zeros(&turns_of(&[49, -100])).unwrap(), (0, 1));
+    }
+
+    #[test]
+    fn other_dials() {
+        assert!(Dial::new(0, 0).is_err());
+        assert!(Dial::new(10, 10).is_err());
+        let mut dial = Dial::new(10, 3).unwrap();
+        assert_eq!(dial.rotate(Move::Left(3)), (0, 0));
+        assert_eq!(dial.rotate(Move::Left(25)), (5, 2));
+        assert_eq!(dial.rotate(Move::Right(15)), (0, 1));
+
+        let mut rng = Rng::new(0);
+        for size in 1..=12 {
+            for start in 0..size {
+                let dial = Dial::new(size, start).unwrap();
+                let rotations = turns_of(
+                    &(0..20)
+                        .map(|_| rng.range(0..=30) * *rng.pick(&[-1, 1]))
+                        .collect::<Vec<isize>>(),
+                );
+                assert_eq!(
+                    count_zeros_from(dial, &rotations).ok(),
+                    count_zeros_by_clicks(dial, &rotations),
+                    "size {size}, start {start}: {rotations:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generated_turns_match_clicks() {
+        for seed in 0..20 {
+            let body = generate(&mut Rng::new(seed), 200);
+            let rotations = parse("generated", &body).unwrap().instructions;
+            assert_eq!(
+                verify_turns(Dial::default(), &rotations),
+                Some(None),
+                "seed {seed}"
+            );
+            let dial = Dial::new(seed as isize + 1, seed as isize / 2).unwrap();
+            assert_eq!(verify_turns(dial, &rotations), Some(None), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn sweep_matches_each_start() {
+        let example = parse("example", EXAMPLE).unwrap().instructions;
+        assert_eq!(sweep_starts(100, &example).unwrap()[50], (3, 6));
+        assert!(sweep_starts(isize::MAX, &example).is_err());
+        let mut rng = Rng::new(1);
+        for size in 1..=12 {
+            let mut rotations = turns_of(
+                &(0..30)
+                    .map(|_| rng.range(0..=40) * *rng.pick(&[-1, 1]))
+                    .collect::<Vec<isize>>(),
+            );
+            if size % 3 == 0 {
+                rotations[10].action = Move::Set(-1);
+            }
+            let sweep = sweep_starts(size, &rotations).unwrap();
+            for (start, &zeros) in sweep.iter().enumerate() {
+                let dial = Dial::new(size, start as isize).unwrap();
+                assert_eq!(
+                    zeros,
+                    count_zeros_from(dial, &rotations).unwrap(),
+                    "size {size}, start {start}"
+                );
+            }
+        }
+        assert!(sweep_starts(0, &example).is_err());
+    }
+
+    #[test]
+    fn trace_turns() {
+        let rotations = parse("example", EXAMPLE).unwrap().instructions;
+        let traced = turns(Dial::default(), &rotations).collect::<Vec<_>>();
+        let first = Turn {
+            line: 1,
+            action: Move::Left(68),
+            from: 50,
+            to: 82,
+            zeros1: 0,
+            zeros2: 1,
+        };
+        assert_eq!(traced[0], first);
+        assert_eq!(traced.iter().map(|t| t.zeros2).sum::<u128>(), 6);
+        assert_eq!(traced[0].action.to_string(), "L68");
+    }
+
+    #[test]
+    fn plans_round_trip() {
+        let targets = [11, 0, 37, 99, 5];
+        for (goal, expected) in
+            [(Goal::Clicks, "L39 L11 R37 L38 R6"), (Goal::Zeros, "L39 L11 R37 R62 L94")]
+        {
+            let moves = plan(Dial::default(), &targets, goal).unwrap();
+            let text = moves.iter().map(Move::to_string).collect::<Vec<_>>();
+            assert_eq!(text.join(" "), expected, "{goal:?}");
+            let body = text.iter().map(|m| format!("{m}\n")).collect::<String>();
+            let rotations = parse("plan", &body).unwrap().instructions;
+            let landed =
+                turns(Dial::default(), &rotations).map(|t| t.to).collect::<Vec<_>>();
+            assert_eq!(landed, targets, "{goal:?}");
+        }
+        assert!(plan(Dial::default(), &[100], Goal::Clicks).is_err());
+
+        // Targets the dial is already on need no move, not an R0.
+        let moves = plan(Dial::default(), &[50, 50, 3, 3, 3], Goal::Clicks).unwrap();
+        assert_eq!(moves, [Move::Left(47)]);
+    }
+
+    #[test]
+    fn extended_syntax() {
+        let body = "\
+# warm up
+R50  # onto zero
+
+[first]
+L10*3
+=-1
+[ second ]
+R1
+=+25
+";
+        let rotations = parse("extended", body).unwrap();
+        let actions = rotations
+            .instructions
+            .iter()
+            .map(|i| (i.line, i.action.to_string(), i.times))
+            .collect::<Vec<_>>();
+        let expected =
+            [(2, "R50", 1), (5, "L10", 3), (6, "=-1", 1), (8, "R1", 1), (9, "=25", 1)];
+        let expected =
+            expected.map(|(line, action, times)| (line, action.to_string(), times));
+        assert_eq!(actions, expected);
+        assert_eq!(rotations.sections, [("first", 4), ("second", 7)]);
+        let dial = Dial::default();
+        assert_eq!(count_zeros_from(dial, &rotations.instructions).unwrap(), (2, 2));
+        assert_eq!(
+            subtotals(dial, &rotations).unwrap(),
+            [("first", 0, 0), ("second", 1, 1)]
+        );
+        assert_eq!(count_zeros_by_clicks(dial, &rotations.instructions), Some((2, 2)));
+        assert_eq!(verify_turns(dial, &rotations.instructions), Some(None));
+    }
+
+    #[test]
+    fn extended_syntax_errors() {
+        for (body, at) in [
+            ("R1\n\nX5\n", "bad:3:1:"),
+            ("R1 # fine\nR2*0\n", "bad:2:3:"),
+            ("[]\n", "bad:1:1:"),
+            ("=\n", "bad:1:2:"),
+            (&format!("L1*{}\n", MAX_REPEATS + 1), "bad:1:3:"),
+        ] {
+            let e = parse("bad", body).unwrap_err().to_string();
+            assert!(e.starts_with(at), "{body:?}: {e}");
+        }
+    }
+}
@@ -1,53 +1,123 @@
-use eyre::{OptionExt, Result, eyre};
-use nom::character::complete::{digit1, one_of};
-use nom::combinator::{all_consuming, map_res};
-use nom::{IResult, Parser};
+use clap::{Parser, ValueEnum};
+use combo::{Dial, Goal};
+use eyre::{Result, eyre};
 
-fn parse_line<'a>(input: &'a str) -> IResult<&'a str, (char, isize)> {
-    all_consuming((one_of("LR"), map_res(digit1, |s: &'a str| s.parse::<isize>())))
-        .parse(input)
+/// Counts the times a dial lands on and passes zero.
+#[derive(Parser)]
+struct Cli {
+    /// Print each rotation with the positions it turns the dial between and the
+    /// zeros it adds to each count.
+    #[arg(long)]
+    trace: bool,
+    /// Check every rotation's counts against turning the dial one click at a time,
+    /// unless that takes too many clicks.
+    #[arg(long)]
+    verify: bool,
+    /// Number of positions on the dial.
+    #[arg(long, default_value_t = 100)]
+    size: isize,
+    /// Position the dial starts at [default: halfway round]
+    #[arg(long)]
+    start: Option<isize>,
+    /// Instead of the answers, print them for every start position, and the starts
+    /// with the fewest and most zeros.
+    #[arg(long, conflicts_with_all = ["start", "trace", "verify"])]
+    sweep: bool,
+    /// Instead of reading inputs, print the moves that turn the dial onto each of
+    /// these positions in turn.
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["inputs", "sweep", "trace", "verify"])]
+    plan: Option<Vec<isize>>,
+    /// What the plan keeps as low as it can [default: clicks]
+    #[arg(long, value_enum, requires = "plan")]
+    minimise: Option<Minimise>,
+    /// Input files; reads standard input if there are none or one is `-`.
+    inputs: Vec<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Minimise {
+    Clicks,
+    /// The clicks that leave the dial on zero.
+    Zeros,
+}
+
+fn print_sweep(zeros: &[(u128, u128)]) {
+    println!("start zeros1 zeros2");
+    for (start, (zeros1, zeros2)) in zeros.iter().enumerate() {
+        println!("{start} {zeros1} {zeros2}");
+    }
+    let zeros1 = zeros.iter().map(|z| z.0).collect::<Vec<_>>();
+    let zeros2 = zeros.iter().map(|z| z.1).collect::<Vec<_>>();
+    for (part, counts) in [("zeros1", zeros1), ("zeros2", zeros2)] {
+        // Ties go to the lowest start.
+        let fewest = (0..counts.len()).min_by_key(|&s| counts[s]).unwrap();
+        let most = (0..counts.len()).rev().max_by_key(|&s| counts[s]).unwrap();
+        println!(
+            "{part}: fewest {} from start {fewest}, most {} from start {most}",
+            counts[fewest], counts[most]
+        );
+    }
 }
 
 fn main() -> Result<()> {
-    let mut args = std::env::args();
-    let fname = args.nth(1).ok_or_eyre("filename was not provided")?;
-    let body: String = std::fs::read_to_string(&fname)?;
-    let mut zeros1 = 0isize;
-    let mut zeros2 = 0isize;
-    let mut dial = 50isize;
-    for (lineno, line) in body.lines().enumerate() {
-        let lineno = lineno + 1;
-        let result = parse_line(line);
-        let (dir, clicks) = match result {
-            Ok((_, v)) => v,
-            Err(e) => match e {
-                nom::Err::Incomplete(_) => unreachable!(),
-                nom::Err::Error(e) | nom::Err::Failure(e) => {
-                    return Err(eyre!("{fname}:{lineno}: parsing failed: {e:?}"));
-                }
-            },
-        };
-        let clicks = match dir {
-            'L' => -clicks,
-            'R' => clicks,
-            _ => unreachable!(),
+    let mut cli = Cli::parse();
+    let dial = Dial::new(cli.size, cli.start.unwrap_or(cli.size / 2))?;
+    if let Some(targets) = &cli.plan {
+        let goal = match cli.minimise {
+            None | Some(Minimise::Clicks) => Goal::Clicks,
+            Some(Minimise::Zeros) => Goal::Zeros,
         };
-        let spins = (dial + clicks).div_euclid(100);
-        let new_dial = (dial + clicks).rem_euclid(100);
-        if new_dial == 0 {
-            zeros1 += 1;
+        for action in combo::plan(dial, targets, goal)? {
+            println!("{action}");
+        }
+        return Ok(());
+    }
+    if cli.inputs.is_empty() {
+        cli.inputs.push(common::STDIN.to_string());
+    }
+    for fname in &cli.inputs {
+        let body = common::read_input(fname)?;
+        let body = common::normalize(&body);
+        let name = common::display_name(fname);
+        let rotations = combo::parse(name, &body)?;
+        if cli.inputs.len() > 1 {
+            println!("{name}:");
+        }
+        let instructions = &rotations.instructions;
+        if cli.trace {
+            for turn in combo::turns(dial, instructions) {
+                println!("{name}:{}: {turn}", turn.line);
+            }
+        }
+        if cli.verify {
+            match combo::verify_turns(dial, instructions) {
+                Some(Some(d)) => {
+                    return Err(eyre!(
+                        "{name}:{}: counted {} but clicking gives {}",
+                        d.fast.line,
+                        d.fast,
+                        d.slow
+                    ));
+                }
+                Some(None) => {
+                    eprintln!("{name}: every move agrees with clicking through it")
+                }
+                None => eprintln!(
+                    "{name}: skipped verifying, as the moves turn more than {} clicks",
+                    combo::MAX_REFERENCE_CLICKS
+                ),
+            }
+        }
+        if cli.sweep {
+            print_sweep(&combo::sweep_starts(cli.size, instructions)?);
+            continue;
         }
-        zeros2 += spins.abs();
-        // Account for when spins is too high due to rounding towards inf:
-        // If going left, when starting from 0
-        // If going right, when landing on 0.
-        if (spins < 0 && dial == 0) || (spins > 0 && new_dial == 0) {
-            zeros2 -= 1;
+        for (label, zeros1, zeros2) in combo::subtotals(dial, &rotations)? {
+            println!("[{label}] {zeros1} {zeros2}");
         }
-        dial = new_dial;
+        let (zeros1, zeros2) = combo::count_zeros_from(dial, instructions)?;
+        println!("{zeros1}");
+        println!("{zeros2}");
     }
-    zeros2 += zeros1; // Landing on zero should also be included.
-    println!("{zeros1}");
-    println!("{zeros2}");
     Ok(())
 }
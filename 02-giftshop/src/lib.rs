@@ -0,0 +1,577 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use common::{Answer, Rng, Solution};
+use eyre::{OptionExt, Result, eyre};
+use nom::character::complete::{char, digit1, newline};
+use nom::combinator::{all_consuming, map_res};
+use nom::multi::separated_list1;
+use nom::sequence::terminated;
+use nom::{IResult, Parser};
+
+pub fn parse_range(input: &str) -> IResult<&str, RangeInclusive<isize>> {
+    (
+        terminated(map_res(digit1, |s: &str| s.parse::<isize>()), char('-')),
+        map_res(digit1, |s: &str| s.parse::<isize>()),
+    )
+        .map(|(start, end)| start..=end)
+        .parse(input)
+}
+
+pub fn parse_file(input: &str) -> IResult<&str, Vec<RangeInclusive<isize>>> {
+    all_consuming(terminated(separated_list1(char(','), parse_range), newline))
+        .parse(input)
+}
+
+pub fn parse(fname: &str, body: &str) -> Result<Vec<RangeInclusive<isize>>> {
+    let ranges = common::finish(fname, body, parse_file(body))?;
+    if let Some(r) = ranges.iter().find(|r| r.is_empty()) {
+        return Err(eyre!(
+            "{fname}: range {}-{} ends before it starts",
+            r.start(),
+            r.end()
+        ));
+    }
+    Ok(ranges)
+}
+
+/// `ranges` in order, with any that share IDs merged, so that each ID is in at most
+/// one of them.
+///
+/// Ranges that only touch are kept apart, so there are fewer ranges than in
+/// `ranges` exactly when some overlap.
+pub fn merge(ranges: &[RangeInclusive<isize>]) -> Vec<RangeInclusive<isize>> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_by_key(|r| (*r.start(), *r.end()));
+    let mut merged: Vec<RangeInclusive<isize>> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+        match merged.last_mut() {
+            Some(last) if last.contains(range.start()) => {
+                *last = *last.start()..=*last.end().max(range.end());
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Which IDs are invalid, for an ID that is some digits written `n` times over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// Written exactly this many times over.
+    Exactly(u32),
+    /// Written at least this many times over.
+    AtLeast(u32),
+    /// Written at least twice over, with a unit of at most this many digits.
+    MaxUnit(u32),
+    /// Written any of these numbers of times over.
+    AnyOf(Vec<u32>),
+}
+
+impl Rule {
+    /// Whether an ID `len` digits long that is a unit written `n` times over breaks
+    /// the rule.
+    fn accepts(&self, len: u32, n: u32) -> bool {
+        n >= 2
+            && match self {
+                Rule::Exactly(k) => n == *k,
+                Rule::AtLeast(k) => n >= *k,
+                Rule::MaxUnit(unit) => len / n <= *unit,
+                Rule::AnyOf(counts) => counts.contains(&n),
+            }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = eyre::Error;
+
+    /// `k` for exactly `k` times over, `k+` for at least `k`, `k,l,...` for any of
+    /// those, or `unit:n` for units of at most `n` digits.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let number = |n: &str| {
+            n.parse::<u32>().map_err(|e| eyre!("rule {s:?}: {n:?} is not a count: {e}"))
+        };
+        let times = |n: &str| match number(n)? {
+            k if k < 2 => Err(eyre!("rule {s:?}: IDs repeat at least twice, not {k}")),
+            k => Ok(k),
+        };
+        if let Some(unit) = s.strip_prefix("unit:") {
+            match number(unit)? {
+                0 => Err(eyre!("rule {s:?}: units are at least one digit")),
+                unit => Ok(Rule::MaxUnit(unit)),
+            }
+        } else if let Some(k) = s.strip_suffix('+') {
+            Ok(Rule::AtLeast(times(k)?))
+        } else if s.contains(',') {
+            Ok(Rule::AnyOf(s.split(',').map(times).collect::<Result<_>>()?))
+        } else {
+            Ok(Rule::Exactly(times(s)?))
+        }
+    }
+}
+
+/// The IDs a [`Rule`] picks out when they are written in `base`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub rule: Rule,
+    pub base: u32,
+}
+
+impl Pattern {
+    pub const PART1: Self = Self { rule: Rule::Exactly(2), base: 10 };
+    pub const PART2: Self = Self { rule: Rule::AtLeast(2), base: 10 };
+
+    /// Fails unless `base` is from 2 to 36, the bases there are digits for.
+    pub fn new(rule: Rule, base: u32) -> Result<Self> {
+        if !(2..=36).contains(&base) {
+            return Err(eyre!("base {base} is not from 2 to 36"));
+        }
+        Ok(Self { rule, base })
+    }
+
+    /// Digits in the longest ID that fits in an `isize`.
+    fn max_digits(&self) -> u32 {
+        isize::MAX.ilog(self.base as isize) + 1
+    }
+
+    /// Whether the rule takes an ID `len` digits long whose shortest unit is
+    /// written `m` times over, as it is then also a longer unit written `n` times
+    /// for each `n` dividing `m`.
+    fn takes(&self, len: u32, m: u32) -> bool {
+        (2..=m).any(|n| m.is_multiple_of(n) && self.rule.accepts(len, n))
+    }
+
+    /// Whether `repeat` breaks the rule.
+    pub fn matches(&self, repeat: &Repeat) -> bool {
+        let len = repeat.unit.ilog(repeat.base as isize) + 1;
+        self.takes(len * repeat.times, repeat.times)
+    }
+
+    /// How many times to add the IDs `len` digits long that are a unit written `k`
+    /// times over, so that every ID is added once if the rule takes it, and not at
+    /// all otherwise.
+    ///
+    /// An ID whose shortest unit is written `m` times over is also a longer unit
+    /// written `k` times for each `k` dividing `m`, so it is in the set for each of
+    /// those `k`. The weights that make those sets add up right are the Möbius
+    /// inversion of whether the rule takes an ID written `m` times.
+    fn weight(&self, len: u32, k: u32) -> i128 {
+        (1..=k)
+            .filter(|d| k.is_multiple_of(*d))
+            .map(|d| mobius((k / d) as usize) * i128::from(self.takes(len, d)))
+            .sum()
+    }
+
+    /// Each number of digits the IDs in `range` can have, with the lowest and
+    /// highest of its IDs that have that many.
+    fn lengths(
+        &self,
+        range: &RangeInclusive<isize>,
+    ) -> impl Iterator<Item = (u32, i128, i128)> {
+        let (start, end) = (*range.start() as i128, *range.end() as i128);
+        let base = i128::from(self.base);
+        (1..=self.max_digits()).filter_map(move |len| {
+            let lo = start.max(base.pow(len - 1));
+            let hi = end.min(base.pow(len) - 1);
+            (lo <= hi).then_some((len, lo, hi))
+        })
+    }
+
+    /// The IDs from `lo` to `hi`, all `unit * k` digits long, that are a `unit`
+    /// digit number written `k` times over: the multiplier, like 1001001, that
+    /// writes a unit out `k` times, and the units whose IDs are in range.
+    fn units(
+        &self,
+        lo: i128,
+        hi: i128,
+        unit: u32,
+        k: u32,
+    ) -> (i128, RangeInclusive<i128>) {
+        let base = i128::from(self.base);
+        let multiplier = (base.pow(unit * k) - 1) / (base.pow(unit) - 1);
+        let first = base.pow(unit - 1).max((lo + multiplier - 1) / multiplier);
+        let last = (base.pow(unit) - 1).min(hi / multiplier);
+        (multiplier, first..=last)
+    }
+
+    /// Adds up `measure` of the IDs in `ranges` that break the rule, given the
+    /// multiplier and units [`Pattern::units`] finds for each length and count.
+    ///
+    /// Rather than looking at every ID, this measures the IDs of each length that
+    /// repeat a unit a given number of times, weighted so that IDs in several of
+    /// those sets are measured once. The work grows with the number of digits
+    /// rather than with the ranges' widths. Returns `None` on overflow.
+    fn total(
+        &self,
+        ranges: &[RangeInclusive<isize>],
+        measure: fn(i128, i128, i128) -> i128,
+    ) -> Option<i128> {
+        let max = self.max_digits();
+        let weights = (0..=max)
+            .map(|len| (0..=len).map(|k| self.weight(len, k)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut total = 0i128;
+        for range in ranges {
+            for (len, lo, hi) in self.lengths(range) {
+                for k in (2..=len).filter(|k| len.is_multiple_of(*k)) {
+                    let weight = weights[len as usize][k as usize];
+                    let (multiplier, units) = self.units(lo, hi, len / k, k);
+                    if weight == 0 || units.is_empty() {
+                        continue;
+                    }
+                    let (first, last) = units.into_inner();
+                    total =
+                        total.checked_add(weight * measure(multiplier, first, last))?;
+                }
+            }
+        }
+        Some(total)
+    }
+}
+
+/// `id` written out in `base`.
+pub fn digits(id: isize, base: u32) -> String {
+    if id == 0 {
+        return "0".to_string();
+    }
+    let mut digits = Vec::new();
+    let mut rest = id.unsigned_abs();
+    while rest > 0 {
+        digits
+            .push(char::from_digit((rest % base as usize) as u32, base).unwrap() as u8);
+        rest /= base as usize;
+    }
+    if id < 0 {
+        digits.push(b'-');
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap()
+}
+
+/// The Möbius function: 0 if `n` has a square factor, otherwise -1 or 1 for an odd
+/// or even number of prime factors.
+fn mobius(mut n: usize) -> i128 {
+    let mut sign = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            sign = -sign;
+        }
+        p += 1;
+    }
+    if n > 1 { -sign } else { sign }
+}
+
+/// Sums the IDs in `ranges` that break `pattern`'s rule.
+pub fn sum_repeated(
+    ranges: &[RangeInclusive<isize>],
+    pattern: &Pattern,
+) -> Result<isize> {
+    let sum = pattern.total(ranges, |multiplier, first, last| {
+        multiplier * (first + last) * (last - first + 1) / 2
+    });
+    sum.and_then(|sum| isize::try_from(sum).ok())
+        .ok_or_eyre("sum of invalid IDs overflowed")
+}
+
+/// Counts the IDs in `ranges` that break `pattern`'s rule.
+pub fn count_repeated(
+    ranges: &[RangeInclusive<isize>],
+    pattern: &Pattern,
+) -> Result<isize> {
+    let count = pattern.total(ranges, |_, first, last| last - first + 1);
+    count
+        .and_then(|count| isize::try_from(count).ok())
+        .ok_or_eyre("too many invalid IDs to count")
+}
+
+/// An invalid ID written as its shortest repeating unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    pub id: isize,
+    pub unit: isize,
+    pub times: u32,
+    pub base: u32,
+}
+
+impl fmt::Display for Repeat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = \"{}\" x{}", self.id, digits(self.unit, self.base), self.times)
+    }
+}
+
+/// Every ID in `range` whose digits in `base` are some unit written at least twice
+/// over, in order.
+pub fn repeats(range: &RangeInclusive<isize>, base: u32) -> Result<Vec<Repeat>> {
+    let pattern = Pattern::new(Rule::AtLeast(2), base)?;
+    let mut found = BTreeMap::new();
+    for (len, lo, hi) in pattern.lengths(range) {
+        // Shortest units first, so each ID keeps the first unit found for it.
+        for unit in (1..len).filter(|u| len.is_multiple_of(*u)) {
+            let times = len / unit;
+            let (multiplier, units) = pattern.units(lo, hi, unit, times);
+            for unit in units {
+                let (id, unit) = ((unit * multiplier) as isize, unit as isize);
+                found.entry(id).or_insert(Repeat { id, unit, times, base });
+            }
+        }
+    }
+    Ok(found.into_values().collect())
+}
+
+/// Most IDs [`sum_by_building`] will look at.
+pub const MAX_REFERENCE_IDS: usize = 10_000_000;
+
+/// The IDs in `ranges` that break `pattern`'s rule, found by building every
+/// candidate it could repeat for each ID, or `None` if the ranges hold more than
+/// [`MAX_REFERENCE_IDS`] IDs.
+fn build(
+    ranges: &[RangeInclusive<isize>],
+    pattern: &Pattern,
+) -> Option<impl Iterator<Item = isize>> {
+    let len = |r: &RangeInclusive<isize>| {
+        let len = r.end().checked_sub(*r.start())?.checked_add(1)?;
+        usize::try_from(len).ok()
+    };
+    let ids = ranges.iter().try_fold(0usize, |ids, r| ids.checked_add(len(r)?));
+    if ids? > MAX_REFERENCE_IDS {
+        return None;
+    }
+    let repeated = |num: &isize| {
+        let s = digits(*num, pattern.base);
+        let len = s.len() as u32;
+        (2..=len)
+            .filter(|&n| len.is_multiple_of(n) && pattern.rule.accepts(len, n))
+            .any(|n| s[..(len / n) as usize].repeat(n as usize) == s)
+    };
+    Some(ranges.iter().cloned().flatten().filter(repeated))
+}
+
+/// [`sum_repeated`] by building every candidate for each ID.
+///
+/// Returns `None` if the ranges hold more than [`MAX_REFERENCE_IDS`] IDs.
+pub fn sum_by_building(
+    ranges: &[RangeInclusive<isize>],
+    pattern: &Pattern,
+) -> Option<Result<isize>> {
+    let sum = build(ranges, pattern)?.map(|num| num as u128).sum::<u128>();
+    Some(isize::try_from(sum).ok().ok_or_eyre("sum of invalid IDs overflowed"))
+}
+
+pub fn part1(ranges: &[RangeInclusive<isize>]) -> Result<isize> {
+    sum_repeated(ranges, &Pattern::PART1)
+}
+
+pub fn part2(ranges: &[RangeInclusive<isize>]) -> Result<isize> {
+    sum_repeated(ranges, &Pattern::PART2)
+}
+
+/// `size` ranges of up to ten digit IDs, each spanning at most 50,000 IDs.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let ranges = (0..size)
+        .map(|_| {
+            let digits = rng.range(1..=10);
+            let start = rng.range(10isize.pow(digits - 1)..=10isize.pow(digits) - 1);
+            format!("{start}-{}", start + rng.range(0..=50_000))
+        })
+        .collect::<Vec<_>>();
+    format!("{}\n", ranges.join(","))
+}
+
+pub struct Giftshop;
+
+impl Solution for Giftshop {
+    type Input<'a> = Vec<RangeInclusive<isize>>;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(ranges: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part1(ranges)?.into())
+    }
+
+    fn part2(ranges: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part2(ranges)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn warnings(ranges: &Self::Input<'_>) -> Vec<String> {
+        if merge(ranges).len() == ranges.len() {
+            return Vec::new();
+        }
+        vec!["ranges overlap, so IDs in more than one are counted once for each".into()]
+    }
+
+    fn reference_part1(ranges: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(sum_by_building(ranges, &Pattern::PART1)?.map(Answer::from))
+    }
+
+    fn reference_part2(ranges: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(sum_by_building(ranges, &Pattern::PART2)?.map(Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day02-example.txt");
+
+    #[test]
+    fn example() {
+        let ranges = parse("example", EXAMPLE).unwrap();
+        assert_eq!(part1(&ranges).unwrap(), 1227775554);
+        assert_eq!(part2(&ranges).unwrap(), 4174379265);
+    }
+
+    #[test]
+    fn building_matches_example() {
+        let ranges = parse("example", EXAMPLE).unwrap();
+        let sum = |pattern| sum_by_building(&ranges, pattern).unwrap().unwrap();
+        assert_eq!(sum(&Pattern::PART1), 1227775554);
+        assert_eq!(sum(&Pattern::PART2), 4174379265);
+    }
+
+    #[test]
+    fn arithmetic_matches_building() {
+        let mut ranges = vec![0..=99_999, 1188511880..=1188511890];
+        for seed in 0..3 {
+            let body = generate(&mut Rng::new(seed), 5);
+            ranges.extend(parse("generated", &body).unwrap());
+        }
+        let patterns = [
+            Pattern::PART1,
+            Pattern::PART2,
+            Pattern::new(Rule::Exactly(3), 10).unwrap(),
+            Pattern::new(Rule::AnyOf(vec![2, 4, 6, 8, 10]), 10).unwrap(),
+        ];
+        for pattern in &patterns {
+            let expected = sum_by_building(&ranges, pattern).unwrap().unwrap();
+            assert_eq!(sum_repeated(&ranges, pattern).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn wide_ranges() {
+        // 11 to 99, then 1010 to 9999 as 101 times 10 to 99.
+        assert_eq!(part1(&[1..=9999]).unwrap(), 11 * 45 + 101 * 4905);
+        assert!(part2(&[0..=isize::MAX]).is_err());
+        // Declined without walking the range.
+        assert!(sum_by_building(&[1..=isize::MAX - 1], &Pattern::PART2).is_none());
+        assert!(sum_by_building(&[0..=isize::MAX], &Pattern::PART2).is_none());
+    }
+
+    #[test]
+    fn repeats_match_sums() {
+        let ranges = parse("example", EXAMPLE).unwrap();
+        let found =
+            ranges.iter().flat_map(|r| repeats(r, 10).unwrap()).collect::<Vec<_>>();
+        let sum = |pattern: &Pattern| {
+            found.iter().filter(|r| pattern.matches(r)).map(|r| r.id).sum::<isize>()
+        };
+        assert_eq!(sum(&Pattern::PART1), part1(&ranges).unwrap());
+        assert_eq!(sum(&Pattern::PART2), part2(&ranges).unwrap());
+
+        let shown = |range| {
+            repeats(&range, 10)
+                .unwrap()
+                .iter()
+                .map(Repeat::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(shown(95..=115), [r#"99 = "9" x2"#, r#"111 = "1" x3"#]);
+        assert_eq!(shown(123123123..=123123123), [r#"123123123 = "123" x3"#]);
+        assert!(
+            !Pattern::PART1.matches(&repeats(&(123123123..=123123123), 10).unwrap()[0])
+        );
+        assert!(Pattern::PART1.matches(&repeats(&(1111..=1111), 10).unwrap()[0]));
+    }
+
+    #[test]
+    fn rules() {
+        assert_eq!("3".parse::<Rule>().unwrap(), Rule::Exactly(3));
+        assert_eq!("2+".parse::<Rule>().unwrap(), Rule::AtLeast(2));
+        assert_eq!("unit:4".parse::<Rule>().unwrap(), Rule::MaxUnit(4));
+        assert_eq!("2,3,5".parse::<Rule>().unwrap(), Rule::AnyOf(vec![2, 3, 5]));
+        for bad in ["1", "x", "0+", "unit:0", "2,,3", ""] {
+            assert!(bad.parse::<Rule>().is_err(), "{bad}");
+        }
+
+        // Up to 9999: 9 IDs like 11 and 90 like 1010 are written twice over, 9 like
+        // 111 three times and 9 like 1111 four times.
+        let ranges = [1..=9999];
+        let count =
+            |rule: Rule| count_repeated(&ranges, &Pattern::new(rule, 10).unwrap());
+        assert_eq!(count(Rule::Exactly(2)).unwrap(), 9 + 90);
+        assert_eq!(count(Rule::Exactly(3)).unwrap(), 9);
+        assert_eq!(count(Rule::AtLeast(2)).unwrap(), 9 + 9 + 90);
+        // 1111 is "1" x4 as well as "11" x2.
+        assert_eq!(count(Rule::Exactly(4)).unwrap(), 9);
+        assert_eq!(count(Rule::MaxUnit(1)).unwrap(), 9 * 3);
+        assert_eq!(count(Rule::AnyOf(vec![3, 4])).unwrap(), 9 + 9);
+    }
+
+    #[test]
+    fn bases() {
+        assert_eq!(digits(255, 16), "ff");
+        assert_eq!(digits(0, 2), "0");
+        assert!(Pattern::new(Rule::Exactly(2), 37).is_err());
+        assert!(Pattern::new(Rule::Exactly(2), 1).is_err());
+
+        // Three hex digits all the same: 0x111 to 0xfff.
+        let hex3 = Pattern::new(Rule::Exactly(3), 16).unwrap();
+        assert_eq!(count_repeated(&[0..=0xfff], &hex3).unwrap(), 15);
+        assert_eq!(sum_repeated(&[0..=0xfff], &hex3).unwrap(), 0x111 * 120);
+        let found = repeats(&(0xabab..=0xabab), 16).unwrap();
+        assert_eq!(found[0].to_string(), r#"43947 = "ab" x2"#);
+
+        let ranges =
+            [0..=20_000, 0xabababa0..=0xababac00, 4_000_000_000..=4_000_005_000];
+        for base in [2, 3, 7, 16, 36] {
+            for rule in [Rule::Exactly(2), Rule::AtLeast(3), Rule::MaxUnit(2)] {
+                let pattern = Pattern::new(rule, base).unwrap();
+                let built = build(&ranges, &pattern).unwrap().count() as isize;
+                assert_eq!(count_repeated(&ranges, &pattern).unwrap(), built);
+                let expected = sum_by_building(&ranges, &pattern).unwrap().unwrap();
+                assert_eq!(sum_repeated(&ranges, &pattern).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn reversed_range() {
+        let e = parse("example", "11-22,30-29,40-50\n").unwrap_err();
+        assert_eq!(e.to_string(), "example: range 30-29 ends before it starts");
+        assert_eq!(parse("example", "7-7\n").unwrap(), [7..=7]);
+    }
+
+    #[test]
+    fn merged_ranges() {
+        assert_eq!(merge(&[20..=30, 1..=5, 3..=8, 6..=6]), [1..=8, 20..=30]);
+        assert_eq!(merge(&[1..=3, 4..=6, 4..=6]), [1..=3, 4..=6]);
+
+        // 11, 22 and 33 are in both ranges.
+        let ranges = [1..=40, 10..=99];
+        assert_eq!(part1(&ranges).unwrap(), 11 * 45 + 66);
+        assert_eq!(part1(&merge(&ranges)).unwrap(), 11 * 45);
+        assert_eq!(Giftshop::warnings(&ranges.to_vec()).len(), 1);
+        assert!(Giftshop::warnings(&merge(&ranges)).is_empty());
+    }
+
+    #[test]
+    fn sum_overflow() {
+        let id = 8888888888888888888;
+        assert_eq!(part2(&[id..=id]).unwrap(), id);
+        assert!(part2(&[id..=id, id..=id]).is_err());
+    }
+}
@@ -1,72 +1,145 @@
 use std::ops::RangeInclusive;
 
-use eyre::{OptionExt, Result, eyre};
-use nom::character::complete::{char, digit1, newline};
-use nom::combinator::{all_consuming, map_res};
-use nom::multi::separated_list1;
-use nom::sequence::terminated;
-use nom::{IResult, Parser};
+use clap::{Parser, ValueEnum};
+use eyre::Result;
+use giftshop::{Giftshop, Pattern, Repeat, Rule};
 
-fn parse_range(input: &str) -> IResult<&str, RangeInclusive<isize>> {
-    (
-        terminated(map_res(digit1, |s: &str| s.parse::<isize>()), char('-')),
-        map_res(digit1, |s: &str| s.parse::<isize>()),
-    )
-        .map(|(start, end)| start..=end)
-        .parse(input)
+/// Sums the invalid IDs in ranges of product IDs.
+#[derive(Parser)]
+struct Cli {
+    /// Instead of the sums, list every invalid ID in each range with the counts and
+    /// sums each part takes from it.
+    #[arg(long, value_enum)]
+    report: Option<Report>,
+    /// Count an ID that several ranges share once, rather than once for each range
+    /// it is in.
+    #[arg(long)]
+    distinct: bool,
+    /// Instead of the two parts, take the IDs written over as this says: `k` for
+    /// exactly k times, `k+` for at least k times, `k,l,...` for any of those, or
+    /// `unit:n` for at least twice with units of at most n digits.
+    #[arg(long)]
+    rule: Option<Rule>,
+    /// Base to write the IDs in when looking for repeats.
+    #[arg(long, default_value_t = 10)]
+    base: u32,
+    /// Print how many IDs are invalid rather than their sum.
+    #[arg(long, conflicts_with = "report")]
+    count: bool,
+    /// Input files; reads standard input if there are none or one is `-`.
+    inputs: Vec<String>,
 }
 
-fn parse_file(input: &str) -> IResult<&str, Vec<RangeInclusive<isize>>> {
-    all_consuming(terminated(separated_list1(char(','), parse_range), newline))
-        .parse(input)
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Report {
+    Text,
+    /// One row per invalid ID, then the count and sum rows for its range.
+    Csv,
 }
 
-fn is_repeated_once(num: isize) -> bool {
-    let s = num.to_string();
-    let half = s.len() / 2;
-    s.len().is_multiple_of(2) && s[..half] == s[half..]
+/// The count and sum of the IDs in `found` that `pattern` takes.
+fn totals(found: &[Repeat], pattern: &Pattern) -> (usize, i128) {
+    let taken = found.iter().filter(|r| pattern.matches(r));
+    taken.fold((0, 0), |(count, sum), r| (count + 1, sum + r.id as i128))
 }
 
-fn is_repeated_any(num: isize) -> bool {
-    let s = num.to_string();
-    let half = s.len() / 2;
-    for base_len in 1..=half {
-        if s.len().is_multiple_of(base_len) {
-            let base = &s[..base_len];
-            if s.matches(base).count() == s.len() / base_len {
-                return true;
+fn print_report(
+    report: Report,
+    ranges: &[RangeInclusive<isize>],
+    patterns: &[(&str, Pattern)],
+    base: u32,
+) -> Result<()> {
+    if report == Report::Csv {
+        let columns = patterns.iter().map(|(label, _)| label.replace(' ', ""));
+        println!("range,kind,id,unit,times,{}", columns.collect::<Vec<_>>().join(","));
+    }
+    for range in ranges {
+        let name = format!("{}-{}", range.start(), range.end());
+        let found = giftshop::repeats(range, base)?;
+        let found = found
+            .into_iter()
+            .filter(|r| patterns.iter().any(|(_, p)| p.matches(r)))
+            .collect::<Vec<_>>();
+        let totals =
+            patterns.iter().map(|(_, p)| totals(&found, p)).collect::<Vec<_>>();
+        match report {
+            Report::Text => {
+                let parts =
+                    patterns.iter().zip(&totals).map(|((label, _), (count, sum))| {
+                        let ids = if *count == 1 { "ID" } else { "IDs" };
+                        format!("{label} {count} {ids} summing to {sum}")
+                    });
+                println!("{name}: {}", parts.collect::<Vec<_>>().join(", "));
+                for r in &found {
+                    let taken = patterns.iter().filter(|(_, p)| p.matches(r));
+                    let labels = taken.map(|(label, _)| *label).collect::<Vec<_>>();
+                    if labels.len() == patterns.len() {
+                        println!("  {r}");
+                    } else {
+                        println!("  {r} ({} only)", labels.join(", "));
+                    }
+                }
+            }
+            Report::Csv => {
+                for r in &found {
+                    let unit = giftshop::digits(r.unit, base);
+                    let flags = patterns.iter().map(|(_, p)| u8::from(p.matches(r)));
+                    let flags = flags.map(|f| f.to_string()).collect::<Vec<_>>();
+                    println!(
+                        "{name},id,{},{unit},{},{}",
+                        r.id,
+                        r.times,
+                        flags.join(",")
+                    );
+                }
+                let counts = totals.iter().map(|(count, _)| count.to_string());
+                println!("{name},count,,,,{}", counts.collect::<Vec<_>>().join(","));
+                let sums = totals.iter().map(|(_, sum)| sum.to_string());
+                println!("{name},sum,,,,{}", sums.collect::<Vec<_>>().join(","));
             }
         }
     }
-    false
+    Ok(())
 }
 
 fn main() -> Result<()> {
-    let mut args = std::env::args();
-    let fname = args.nth(1).ok_or_eyre("filename was not provided")?;
-    let body: String = std::fs::read_to_string(&fname)?;
-    let ranges = match parse_file(&body) {
-        Ok((_, v)) => v,
-        Err(e) => match e {
-            nom::Err::Incomplete(_) => unreachable!(),
-            nom::Err::Error(e) | nom::Err::Failure(e) => {
-                return Err(eyre!("{fname}: parsing failed: {e:?}"));
-            }
-        },
+    let mut cli = Cli::parse();
+    let plain = cli.rule.is_none() && cli.base == 10 && !cli.count;
+    if plain && cli.report.is_none() && !cli.distinct {
+        return common::solve_files::<Giftshop>(&cli.inputs);
+    }
+    let patterns = match cli.rule.clone() {
+        Some(rule) => vec![("rule", Pattern::new(rule, cli.base)?)],
+        None => vec![
+            ("part 1", Pattern::new(Pattern::PART1.rule, cli.base)?),
+            ("part 2", Pattern::new(Pattern::PART2.rule, cli.base)?),
+        ],
     };
-    let first = ranges
-        .iter()
-        .cloned()
-        .flatten()
-        .filter(|num| is_repeated_once(*num))
-        .sum::<isize>();
-    println!("{first}");
-    let second = ranges
-        .iter()
-        .cloned()
-        .flatten()
-        .filter(|num| is_repeated_any(*num))
-        .sum::<isize>();
-    println!("{second}");
+    if cli.inputs.is_empty() {
+        cli.inputs.push(common::STDIN.to_string());
+    }
+    for fname in &cli.inputs {
+        let body = common::read_input(fname)?;
+        let body = common::normalize(&body);
+        let name = common::display_name(fname);
+        let mut ranges = giftshop::parse(name, &body)?;
+        if cli.distinct {
+            ranges = giftshop::merge(&ranges);
+        }
+        if cli.inputs.len() > 1 {
+            println!("{name}:");
+        }
+        if let Some(report) = cli.report {
+            print_report(report, &ranges, &patterns, cli.base)?;
+            continue;
+        }
+        for (_, pattern) in &patterns {
+            if cli.count {
+                println!("{}", giftshop::count_repeated(&ranges, pattern)?);
+            } else {
+                println!("{}", giftshop::sum_repeated(&ranges, pattern)?);
+            }
+        }
+    }
     Ok(())
 }
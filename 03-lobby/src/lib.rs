@@ -0,0 +1,198 @@
+#![allow(dead_code)]
+#![allow(clippy::needless_range_loop)]
+use common::{Answer, Rng, Solution};
+use eyre::{Result, eyre};
+use nom::character::complete::one_of;
+use nom::combinator::{all_consuming, map_opt};
+use nom::multi::many1;
+use nom::{IResult, Parser};
+
+pub fn parse_line(input: &str) -> IResult<&str, Vec<u8>> {
+    all_consuming(many1(map_opt(one_of("0123456789"), |c| {
+        c.to_digit(10).map(|d| d as u8)
+    })))
+    .parse(input)
+}
+
+fn max_joltage(
+    digits: impl IntoIterator<Item = u8, IntoIter: DoubleEndedIterator + Clone>,
+) -> Option<u8> {
+    let iter = digits.into_iter();
+    let first = iter.clone().rev().enumerate().skip(1).max_by_key(|(_, d)| *d);
+    if let Some((i, tens)) = first {
+        let second = iter.rev().take(i).max();
+        second.map(|ones| tens * 10 + ones)
+    } else {
+        None
+    }
+}
+
+pub fn dangerous_joltage<const N: usize>(
+    digits: impl IntoIterator<
+        Item = u8,
+        IntoIter: ExactSizeIterator + DoubleEndedIterator + Clone,
+    >,
+) -> Option<isize> {
+    let iter = digits.into_iter();
+    if iter.len() >= N {
+        let mut places = [0u8; N];
+        let mut pos = iter.len();
+        for i in 0..N {
+            (pos, places[i]) = iter
+                .clone()
+                .rev()
+                .enumerate()
+                .take(pos)
+                .skip(N - 1 - i)
+                .max_by_key(|(_, d)| *d)?;
+        }
+        let mut result = 0isize;
+        for d in places {
+            result = result * 10 + isize::from(d);
+        }
+        Some(result)
+    } else {
+        None
+    }
+}
+
+/// [`dangerous_joltage`] by deciding for every battery, last to first, whether to
+/// take or skip it, keeping the best joltage of each length found so far.
+pub fn joltage_by_choices(bank: &[u8], n: usize) -> Option<isize> {
+    let mut best = vec![None; n + 1];
+    best[0] = Some(0isize);
+    for &d in bank.iter().rev() {
+        for k in (1..=n).rev() {
+            let place = 10isize.pow(u32::try_from(k - 1).ok()?);
+            let take = best[k - 1].map(|rest| isize::from(d) * place + rest);
+            best[k] = best[k].max(take);
+        }
+    }
+    best[n]
+}
+
+/// Batteries part 2 turns on in each bank, so the fewest a bank can hold.
+pub const BATTERIES: usize = 12;
+
+/// Parses one bank of at least [`BATTERIES`] battery digits per line.
+pub fn parse(fname: &str, body: &str) -> Result<Vec<Vec<u8>>> {
+    body.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let bank = common::finish(fname, body, parse_line(line))?;
+            if bank.len() < BATTERIES {
+                return Err(eyre!("{fname}:{}: too short", i + 1));
+            }
+            Ok(bank)
+        })
+        .collect()
+}
+
+fn sum_joltages<const N: usize>(banks: &[Vec<u8>]) -> Result<isize> {
+    banks.iter().enumerate().try_fold(0isize, |acc, (i, bank)| {
+        let joltage = dangerous_joltage::<N>(bank.iter().cloned())
+            .ok_or_else(|| eyre!("bank {} is too short", i + 1))?;
+        Ok(acc + joltage)
+    })
+}
+
+fn sum_by_choices(banks: &[Vec<u8>], n: usize) -> Result<isize> {
+    banks.iter().enumerate().try_fold(0isize, |acc, (i, bank)| {
+        let joltage = joltage_by_choices(bank, n)
+            .ok_or_else(|| eyre!("bank {} is too short", i + 1))?;
+        Ok(acc + joltage)
+    })
+}
+
+pub fn part1(banks: &[Vec<u8>]) -> Result<isize> {
+    // max_joltage() gives the same result for two batteries.
+    sum_joltages::<2>(banks)
+}
+
+pub fn part2(banks: &[Vec<u8>]) -> Result<isize> {
+    sum_joltages::<BATTERIES>(banks)
+}
+
+/// `size` banks of 12 to 100 batteries.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut body = String::new();
+    for _ in 0..size {
+        let len = rng.range(12..=100);
+        body.extend((0..len).map(|_| char::from(b'0' + rng.range(1..=9))));
+        body.push('\n');
+    }
+    body
+}
+
+pub struct Lobby;
+
+impl Solution for Lobby {
+    type Input<'a> = Vec<Vec<u8>>;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(banks: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part1(banks)?.into())
+    }
+
+    fn part2(banks: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part2(banks)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part1(banks: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(sum_by_choices(banks, 2).map(Answer::from))
+    }
+
+    fn reference_part2(banks: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(sum_by_choices(banks, BATTERIES).map(Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day03-example.txt");
+
+    #[test]
+    fn example() {
+        let banks = parse("example", EXAMPLE).unwrap();
+        assert_eq!(part1(&banks).unwrap(), 357);
+        assert_eq!(part2(&banks).unwrap(), 3121910778619);
+    }
+
+    #[test]
+    fn short_bank() {
+        let e = parse("short", "987654321111111\n12345678901\n").unwrap_err();
+        assert_eq!(e.to_string(), "short:2: too short");
+    }
+
+    #[test]
+    fn joltage_keeps_digit_order() {
+        assert_eq!(dangerous_joltage::<2>([8, 1, 9]), Some(89));
+        assert_eq!(dangerous_joltage::<2>([9, 1, 8]), Some(98));
+        assert_eq!(dangerous_joltage::<3>([1, 2, 3]), Some(123));
+        assert_eq!(dangerous_joltage::<3>([1, 2]), None);
+    }
+
+    #[test]
+    fn choices_match_greedy() {
+        let banks = [&[8, 1, 9][..], &[9, 1, 8], &[1, 2, 3], &[1, 2], &[5, 9, 5, 9, 1]];
+        for bank in banks {
+            assert_eq!(
+                joltage_by_choices(bank, 2),
+                dangerous_joltage::<2>(bank.iter().cloned())
+            );
+            assert_eq!(
+                joltage_by_choices(bank, 3),
+                dangerous_joltage::<3>(bank.iter().cloned())
+            );
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use common::{Answer, Grid, Rng, Solution};
+use eyre::{Result, eyre};
+
+/// Reads the floor plan, with `@` marking a roll of paper and `.` an empty spot.
+pub fn parse_file(body: &str) -> Result<Grid<bool>> {
+    Grid::parse_with(body, |c| match c {
+        '@' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(eyre!("unknown cell {c:?}")),
+    })
+}
+
+pub fn is_accessible(pos: (usize, usize), rolls: &Grid<bool>) -> bool {
+    rolls.neighbours8(pos).filter(|&p| rolls[p]).count() < 4
+}
+
+fn accessible_rolls(rolls: &Grid<bool>) -> Vec<(usize, usize)> {
+    rolls
+        .indexed_iter()
+        .filter_map(
+            |(pos, c)| if *c && is_accessible(pos, rolls) { Some(pos) } else { None },
+        )
+        .collect::<Vec<_>>()
+}
+
+pub fn parse(fname: &str, body: &str) -> Result<Grid<bool>> {
+    parse_file(body).map_err(|e| eyre!("{fname}:{e}"))
+}
+
+pub fn part1(rolls: &Grid<bool>) -> usize {
+    accessible_rolls(rolls).len()
+}
+
+pub fn part2(rolls: &Grid<bool>) -> usize {
+    let mut rolls = rolls.clone();
+    let mut count = 0usize;
+    loop {
+        let removed = accessible_rolls(&rolls);
+        if removed.is_empty() {
+            return count;
+        }
+        count += removed.len();
+        for pos in removed {
+            rolls[pos] = false;
+        }
+    }
+}
+
+/// Both parts by counting each roll's neighbours from scratch, then removing one
+/// accessible roll at a time, always the first in reading order, until none is left.
+pub fn remove_one_at_a_time(rolls: &Grid<bool>) -> (usize, usize) {
+    let mut rolls = rolls.clone();
+    let (rows, cols) = rolls.dim();
+    let accessible = |rolls: &Grid<bool>, &(y, x): &(usize, usize)| {
+        let mut neighbours = 0;
+        for ny in y.saturating_sub(1)..=(y + 1).min(rows - 1) {
+            for nx in x.saturating_sub(1)..=(x + 1).min(cols - 1) {
+                if (ny, nx) != (y, x) && rolls[(ny, nx)] {
+                    neighbours += 1;
+                }
+            }
+        }
+        rolls[(y, x)] && neighbours < 4
+    };
+    let spots = || (0..rows).flat_map(|y| (0..cols).map(move |x| (y, x)));
+    let first = spots().filter(|pos| accessible(&rolls, pos)).count();
+    let mut removed = 0;
+    while let Some(pos) = spots().find(|pos| accessible(&rolls, pos)) {
+        rolls[pos] = false;
+        removed += 1;
+    }
+    (first, removed)
+}
+
+/// A `size` by `size` floor with roughly 60% of the spots holding a roll.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut body = String::new();
+    for _ in 0..size {
+        body.extend((0..size).map(|_| if rng.chance(0.6) { '@' } else { '.' }));
+        body.push('\n');
+    }
+    body
+}
+
+pub struct Paper;
+
+impl Solution for Paper {
+    type Input<'a> = Grid<bool>;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(rolls: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part1(rolls).into())
+    }
+
+    fn part2(rolls: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part2(rolls).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part1(rolls: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(Ok(remove_one_at_a_time(rolls).0.into()))
+    }
+
+    fn reference_part2(rolls: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(Ok(remove_one_at_a_time(rolls).1.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day04-example.txt");
+
+    #[test]
+    fn example() {
+        let rolls = parse("example", EXAMPLE).unwrap();
+        assert_eq!(part1(&rolls), 13);
+        assert_eq!(part2(&rolls), 43);
+        assert_eq!(remove_one_at_a_time(&rolls), (13, 43));
+    }
+
+    #[test]
+    fn ragged_lines() {
+        assert!(parse("ragged", "@@.\n@.\n").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+use std::cmp::max;
+use std::ops::RangeInclusive;
+
+use common::{Answer, Rng, Solution};
+use eyre::{Result, eyre};
+use nom::character::complete::{char, digit1, newline};
+use nom::combinator::{all_consuming, map_res, opt};
+use nom::multi::separated_list1;
+use nom::sequence::terminated;
+use nom::{IResult, Parser};
+
+pub fn parse_number(input: &str) -> IResult<&str, isize> {
+    map_res(digit1, |s: &str| s.parse::<isize>()).parse(input)
+}
+
+pub fn parse_range(input: &str) -> IResult<&str, RangeInclusive<isize>> {
+    (terminated(parse_number, char('-')), parse_number)
+        .map(|(start, end)| start..=end)
+        .parse(input)
+}
+
+pub fn parse_file(
+    input: &str,
+) -> IResult<&str, (Vec<RangeInclusive<isize>>, Vec<isize>)> {
+    let ranges = separated_list1(newline, parse_range);
+    let ingredients = separated_list1(newline, parse_number);
+    all_consuming((
+        terminated(ranges, (newline, newline)),
+        terminated(ingredients, opt(newline)),
+    ))
+    .parse(input)
+}
+
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    pub ranges: Vec<RangeInclusive<isize>>,
+    pub ingredients: Vec<isize>,
+}
+
+pub fn parse(fname: &str, body: &str) -> Result<Inventory> {
+    let (ranges, ingredients) = common::finish(fname, body, parse_file(body))?;
+    Ok(Inventory { ranges, ingredients })
+}
+
+/// Counts the IDs covered by at least one of `ranges`.
+pub fn total_fresh(ranges: &[RangeInclusive<isize>]) -> Result<isize> {
+    let mut ranges = ranges.to_vec();
+    ranges.sort_by_key(|r| (*r.start(), *r.end()));
+    if ranges.is_empty() {
+        return Err(eyre!("no input ranges"));
+    }
+    let add = |total: isize, range: &RangeInclusive<isize>| {
+        (range.end() - range.start())
+            .checked_add(1)
+            .and_then(|len| len.checked_add(total))
+            .ok_or_else(|| eyre!("too many fresh IDs to count"))
+    };
+    let mut total = 0isize;
+    let mut current = ranges[0].clone();
+    for range in ranges {
+        if current.contains(range.start()) {
+            current = *current.start()..=max(*current.end(), *range.end())
+        } else {
+            total = add(total, &current)?;
+            current = range;
+        }
+    }
+    add(total, &current)
+}
+
+/// [`total_fresh`] by cutting the IDs into pieces at every range's ends and adding
+/// up the pieces that some range covers.
+pub fn fresh_by_pieces(ranges: &[RangeInclusive<isize>]) -> Result<isize> {
+    if ranges.is_empty() {
+        return Err(eyre!("no input ranges"));
+    }
+    let mut cuts = ranges
+        .iter()
+        .flat_map(|r| [*r.start() as i128, *r.end() as i128 + 1])
+        .collect::<Vec<_>>();
+    cuts.sort_unstable();
+    cuts.dedup();
+    let total = cuts
+        .windows(2)
+        .filter(|w| ranges.iter().any(|r| r.contains(&(w[0] as isize))))
+        .map(|w| w[1] - w[0])
+        .sum::<i128>();
+    isize::try_from(total).map_err(|_| eyre!("too many fresh IDs to count"))
+}
+
+pub fn part1(inventory: &Inventory) -> usize {
+    inventory
+        .ingredients
+        .iter()
+        .filter_map(|id| inventory.ranges.iter().find(|r| r.contains(id)))
+        .count()
+}
+
+pub fn part2(inventory: &Inventory) -> Result<isize> {
+    total_fresh(&inventory.ranges)
+}
+
+/// `size` fresh ranges followed by `size` ingredient IDs, about half of them fresh.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let ranges = (0..size.max(1))
+        .map(|_| {
+            let start = rng.range(1isize..=1_000_000_000_000_000);
+            start..=start + rng.range(0..=1_000_000_000_000)
+        })
+        .collect::<Vec<_>>();
+    let mut body = ranges
+        .iter()
+        .map(|r| format!("{}-{}\n", r.start(), r.end()))
+        .collect::<String>();
+    body.push('\n');
+    for _ in 0..size.max(1) {
+        let id = if rng.chance(0.5) {
+            let range = rng.pick(&ranges);
+            rng.range(*range.start()..=*range.end())
+        } else {
+            rng.range(1isize..=1_001_000_000_000_000)
+        };
+        body += &format!("{id}\n");
+    }
+    body
+}
+
+pub struct Cafeteria;
+
+impl Solution for Cafeteria {
+    type Input<'a> = Inventory;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(inventory: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part1(inventory).into())
+    }
+
+    fn part2(inventory: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part2(inventory)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part1(inventory: &Self::Input<'_>) -> Option<Result<Answer>> {
+        let fresh = inventory.ingredients.iter().filter(|&&id| {
+            inventory.ranges.iter().any(|r| *r.start() <= id && id <= *r.end())
+        });
+        Some(Ok(fresh.count().into()))
+    }
+
+    fn reference_part2(inventory: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(fresh_by_pieces(&inventory.ranges).map(Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day05-example.txt");
+
+    #[test]
+    fn example() {
+        let inventory = parse("example", EXAMPLE).unwrap();
+        assert_eq!(part1(&inventory), 3);
+        assert_eq!(part2(&inventory).unwrap(), 14);
+    }
+
+    #[test]
+    fn merge_overlapping() {
+        assert_eq!(total_fresh(&[1..=5, 3..=8]).unwrap(), 8);
+        assert_eq!(total_fresh(&[3..=8, 1..=5]).unwrap(), 8);
+    }
+
+    #[test]
+    fn merge_contained_and_duplicate() {
+        assert_eq!(total_fresh(&[1..=10, 2..=3, 4..=4]).unwrap(), 10);
+        assert_eq!(total_fresh(&[4..=6, 4..=6]).unwrap(), 3);
+    }
+
+    #[test]
+    fn merge_touching_and_disjoint() {
+        assert_eq!(total_fresh(&[1..=3, 4..=6]).unwrap(), 6);
+        assert_eq!(total_fresh(&[1..=3, 3..=6]).unwrap(), 6);
+        assert_eq!(total_fresh(&[10..=12, 1..=3]).unwrap(), 6);
+    }
+
+    #[test]
+    fn too_many_fresh() {
+        assert_eq!(total_fresh(&[1..=isize::MAX]).unwrap(), isize::MAX);
+        assert!(total_fresh(&[0..=isize::MAX]).is_err());
+        assert!(total_fresh(&[0..=2, 3..=isize::MAX]).is_err());
+    }
+
+    #[test]
+    fn pieces_match_merging() {
+        let cases = [
+            &[1..=5, 3..=8][..],
+            &[1..=10, 2..=3, 4..=4],
+            &[4..=6, 4..=6],
+            &[10..=12, 1..=3, 3..=6],
+            &[1..=isize::MAX],
+        ];
+        for ranges in cases {
+            assert_eq!(fresh_by_pieces(ranges).unwrap(), total_fresh(ranges).unwrap());
+        }
+        assert!(fresh_by_pieces(&[0..=2, 3..=isize::MAX]).is_err());
+    }
+
+    #[test]
+    fn merge_nothing() {
+        assert!(total_fresh(&[]).is_err());
+    }
+}
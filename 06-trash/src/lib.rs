@@ -0,0 +1,339 @@
+use std::str::FromStr;
+
+use common::{Answer, Rng, Solution};
+use eyre::{Result, eyre};
+use ndarray::{Array, Array2, Axis};
+use nom::bytes::complete::take_while;
+use nom::character::complete::one_of;
+use nom::combinator::{map_res, recognize};
+use nom::multi::many1;
+use nom::{IResult, Parser};
+
+#[derive(Debug)]
+pub enum OpKind {
+    Add,
+    Multiply,
+}
+
+#[derive(Debug)]
+pub struct Op {
+    pub len: u8,
+    pub kind: OpKind,
+}
+
+impl FromStr for Op {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let kind = match chars.nth(0) {
+            Some('*') => Ok(OpKind::Multiply),
+            Some('+') => Ok(OpKind::Add),
+            Some(_) => Err(eyre!("unknown operator {s:?}")),
+            None => Err(eyre!("column too short: {s:?}")),
+        }?;
+        // s contains the space between columns but one character was consumed above.
+        let len = u8::try_from(chars.count())?;
+        if len > 0 {
+            Ok(Self { len, kind })
+        } else {
+            Err(eyre!("column too short: {s:?}"))
+        }
+    }
+}
+
+pub fn parse_op_line(input: &str) -> IResult<&str, Vec<Op>> {
+    // newline counts as trailing space for last entry, needed for from_str above.
+    let parse_op = map_res(
+        recognize((one_of("+*"), take_while(|c| " \n".contains(c)))),
+        |s: &str| s.parse::<Op>(),
+    );
+    many1(parse_op).parse(input)
+}
+
+/// Splits each line into one field per column.
+///
+/// Lines may stop short of the last columns when their trailing spaces were trimmed;
+/// the missing part of each field is left out.
+pub fn parse_num_lines<'a>(cols: &[Op], input: &'a str) -> Result<Vec<&'a str>> {
+    let mut result = Vec::with_capacity(cols.len() * 4); // cheat
+    for (lineno, line) in input.lines().enumerate() {
+        let mut start = 0usize;
+        for col in cols {
+            let end = (start + usize::from(col.len)).min(line.len());
+            let field = line.get(start.min(end)..end).ok_or_else(|| {
+                eyre!("{}: column {} is not ASCII", lineno + 1, start + 1)
+            })?;
+            result.push(field);
+            start += usize::from(col.len) + 1; // skip space between columns
+        }
+        if start < line.len() {
+            return Err(eyre!(
+                "{}: did not parse enough {}",
+                lineno + 1,
+                line.len() - start
+            ));
+        }
+    }
+    Ok(result)
+}
+
+impl OpKind {
+    /// Adds or multiplies `nums`, failing instead of overflowing.
+    pub fn apply(&self, mut nums: impl Iterator<Item = usize>) -> Result<usize> {
+        let result = match self {
+            OpKind::Add => nums.try_fold(0usize, usize::checked_add),
+            OpKind::Multiply => nums.try_fold(1usize, usize::checked_mul),
+        };
+        result.ok_or_else(|| eyre!("result of {self:?} is too large"))
+    }
+}
+
+fn checked_add(sum: usize, value: usize) -> Result<usize> {
+    sum.checked_add(value).ok_or_else(|| eyre!("grand total is too large"))
+}
+
+pub fn part1(Worksheet { ops, num_strs }: &Worksheet) -> Result<usize> {
+    let columns = ops.len();
+    let rows = num_strs.len() / columns;
+    let num_vec = num_strs
+        .iter()
+        .map(|s| Ok(s.trim().parse::<usize>()?))
+        .collect::<Result<Vec<_>>>()?;
+    let nums = Array2::from_shape_vec((rows, columns), num_vec)?;
+    ops.iter().zip(nums.axis_iter(Axis(1))).try_fold(0usize, |sum, (op, axis)| {
+        checked_add(sum, op.kind.apply(axis.iter().copied())?)
+    })
+}
+
+pub fn part2(Worksheet { ops, num_strs }: &Worksheet) -> Result<usize> {
+    let columns = ops.len();
+    let rows = num_strs.len() / columns;
+    let fields =
+        Array::from_iter(num_strs.iter()).into_shape_with_order((rows, columns))?;
+    ops.iter().zip(fields.axis_iter(Axis(1))).try_fold(0usize, |sum, (op, axis)| {
+        let mut nums = Vec::with_capacity(op.len.into());
+        for i in 0..op.len.into() {
+            // A column without any digits holds no number rather than zero.
+            let mut num = None;
+            for field in axis.iter() {
+                if let Some(digit) = field.chars().nth(i).and_then(|c| c.to_digit(10)) {
+                    let n = num.unwrap_or(0usize);
+                    num = Some(
+                        n.checked_mul(10)
+                            .and_then(|n| n.checked_add(digit as usize))
+                            .ok_or_else(|| eyre!("number in column is too large"))?,
+                    );
+                }
+            }
+            nums.extend(num);
+        }
+        checked_add(sum, op.kind.apply(nums.into_iter())?)
+    })
+}
+
+/// The operator line and the number fields above it, one per column and row.
+#[derive(Debug)]
+pub struct Worksheet<'a> {
+    pub ops: Vec<Op>,
+    pub num_strs: Vec<&'a str>,
+}
+
+impl Worksheet<'_> {
+    /// The number lines, with every field padded to its column's width.
+    pub fn rows(&self) -> Vec<String> {
+        self.num_strs
+            .chunks(self.ops.len())
+            .map(|fields| {
+                let padded = fields.iter().zip(&self.ops).map(|(field, op)| {
+                    format!("{field:<width$}", width = usize::from(op.len))
+                });
+                padded.collect::<Vec<_>>().join(" ")
+            })
+            .collect()
+    }
+}
+
+/// Part 1 from the worksheet's text, taking each row's numbers in order.
+pub fn part1_by_rows(worksheet: &Worksheet) -> Result<usize> {
+    let mut problems = vec![Vec::new(); worksheet.ops.len()];
+    for row in worksheet.rows() {
+        let nums = row.split_whitespace().collect::<Vec<_>>();
+        if nums.len() != problems.len() {
+            return Err(eyre!("{row:?}: expected {} numbers", problems.len()));
+        }
+        for (problem, num) in problems.iter_mut().zip(nums) {
+            problem.push(num.parse::<usize>()?);
+        }
+    }
+    worksheet.ops.iter().zip(problems).try_fold(0, |sum, (op, nums)| {
+        checked_add(sum, op.kind.apply(nums.into_iter())?)
+    })
+}
+
+/// Part 2 from the worksheet's text, reading each character column from top to
+/// bottom as a number, right to left within each problem.
+pub fn part2_by_columns(worksheet: &Worksheet) -> Result<usize> {
+    let rows = worksheet.rows();
+    let mut sum = 0;
+    let mut start = 0;
+    for op in &worksheet.ops {
+        let end = start + usize::from(op.len);
+        let mut nums = Vec::new();
+        for x in (start..end).rev() {
+            let digits = rows
+                .iter()
+                .filter_map(|row| row.as_bytes().get(x).copied())
+                .filter(u8::is_ascii_digit)
+                .map(char::from)
+                .collect::<String>();
+            if !digits.is_empty() {
+                nums.push(digits.parse::<usize>()?);
+            }
+        }
+        sum = checked_add(sum, op.kind.apply(nums.into_iter())?)?;
+        start = end + 1;
+    }
+    Ok(sum)
+}
+
+pub fn parse<'a>(fname: &str, body: &'a str) -> Result<Worksheet<'a>> {
+    let ops_start = body
+        .find(['*', '+'])
+        .ok_or_else(|| eyre!("{fname}: could not find op line"))?;
+    let mut ops = common::finish(fname, body, parse_op_line(&body[ops_start..]))?;
+    // Without trailing spaces the op line cannot say how wide the last column is,
+    // but the longest number line still reaches its end.
+    let last_start = ops.iter().rev().skip(1).map(|op| usize::from(op.len) + 1).sum();
+    let widest = body[..ops_start].lines().map(str::len).max().unwrap_or(0);
+    if let Some(last) = ops.last_mut() {
+        let len = widest.saturating_sub(last_start);
+        last.len = last.len.max(u8::try_from(len).map_err(|e| eyre!("{fname}: {e}"))?);
+    }
+    let num_strs =
+        parse_num_lines(&ops, &body[..ops_start]).map_err(|e| eyre!("{fname}:{e}"))?;
+    Ok(Worksheet { ops, num_strs })
+}
+
+/// A worksheet of four rows and `size` problems of up to three digit numbers, each
+/// aligned left or right within its column.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut rows = vec![String::new(); 5];
+    for col in 0..size.max(1) {
+        let width = rng.range(1..=3);
+        let left = rng.chance(0.5);
+        for row in &mut rows[..4] {
+            let len = rng.range(1..=width);
+            let num = (0..len)
+                .map(|_| char::from(rng.range(b'1'..=b'9')))
+                .collect::<String>();
+            *row +=
+                &if left { format!("{num:<width$}") } else { format!("{num:>width$}") };
+        }
+        rows[4] += &format!("{:<width$}", rng.pick(&['*', '+']));
+        if col + 1 < size {
+            rows.iter_mut().for_each(|row| row.push(' '));
+        }
+    }
+    rows.iter().map(|row| format!("{row}\n")).collect()
+}
+
+pub struct Trash;
+
+impl Solution for Trash {
+    type Input<'a> = Worksheet<'a>;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(worksheet: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part1(worksheet)?.into())
+    }
+
+    fn part2(worksheet: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part2(worksheet)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part1(worksheet: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(part1_by_rows(worksheet).map(Answer::from))
+    }
+
+    fn reference_part2(worksheet: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(part2_by_columns(worksheet).map(Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day06-example.txt");
+
+    #[test]
+    fn example() {
+        let worksheet = parse("example", EXAMPLE).unwrap();
+        assert_eq!(part1(&worksheet).unwrap(), 4277556);
+        assert_eq!(part2(&worksheet).unwrap(), 3263827);
+    }
+
+    #[test]
+    fn op_width_excludes_operator() {
+        let op = "*   ".parse::<Op>().unwrap();
+        assert_eq!(op.len, 3);
+        assert!(matches!(op.kind, OpKind::Multiply));
+        let op = "+ ".parse::<Op>().unwrap();
+        assert_eq!(op.len, 1);
+        assert!(matches!(op.kind, OpKind::Add));
+    }
+
+    #[test]
+    fn op_needs_a_column() {
+        assert!("*".parse::<Op>().is_err());
+        assert!("".parse::<Op>().is_err());
+        assert!("-  ".parse::<Op>().is_err());
+    }
+
+    #[test]
+    fn op_line_widths() {
+        let (_, ops) = parse_op_line("*   +   *   +  ").unwrap();
+        assert_eq!(ops.iter().map(|op| op.len).collect::<Vec<_>>(), [3, 3, 3, 2]);
+    }
+
+    #[test]
+    fn trimmed_lines() {
+        let trimmed = EXAMPLE
+            .lines()
+            .map(|l| l.trim_end().to_string() + "\n")
+            .collect::<String>();
+        let worksheet = parse("trimmed", &trimmed).unwrap();
+        assert_eq!(worksheet.ops.last().unwrap().len, 3);
+        assert_eq!(part1(&worksheet).unwrap(), 4277556);
+        assert_eq!(part2(&worksheet).unwrap(), 3263827);
+    }
+
+    #[test]
+    fn text_matches_example() {
+        let worksheet = parse("example", EXAMPLE).unwrap();
+        assert_eq!(part1_by_rows(&worksheet).unwrap(), 4277556);
+        assert_eq!(part2_by_columns(&worksheet).unwrap(), 3263827);
+    }
+
+    #[test]
+    fn blank_column_is_not_zero() {
+        let worksheet = parse("blank", "1  4\n2  5\n*  +\n").unwrap();
+        assert_eq!(part2(&worksheet).unwrap(), 12 + 45);
+        assert_eq!(part2_by_columns(&worksheet).unwrap(), 12 + 45);
+    }
+
+    #[test]
+    fn product_overflow() {
+        assert_eq!(OpKind::Multiply.apply([2, 3, 4].into_iter()).unwrap(), 24);
+        assert!(OpKind::Multiply.apply([usize::MAX, 2].into_iter()).is_err());
+        assert!(OpKind::Add.apply([usize::MAX, 1].into_iter()).is_err());
+    }
+}
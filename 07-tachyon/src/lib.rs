@@ -0,0 +1,304 @@
+use std::collections::HashSet;
+
+use common::{Answer, Grid, Rng, Solution};
+use eyre::{OptionExt, Result, eyre};
+use ndarray::{Array2, ArrayRef2, Axis};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Element {
+    Empty,
+    Start,
+    Splitter,
+    //Beam
+}
+
+impl TryFrom<char> for Element {
+    type Error = eyre::Error;
+
+    fn try_from(value: char) -> std::result::Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Empty),
+            'S' => Ok(Self::Start),
+            '^' => Ok(Self::Splitter),
+            _ => Err(eyre!("unknown element: {value}"))
+        }
+    }
+}
+
+impl From<Element> for char {
+    fn from(value: Element) -> Self {
+        match value {
+            Element::Empty => '.',
+            Element::Start => 'S',
+            Element::Splitter => '^',
+        }
+    }
+}
+
+pub fn parse_file(input: &str) -> Result<(Grid<Element>, (usize, usize))> {
+    let grid = Grid::<Element>::parse(input)?;
+    let mut starts =
+        grid.indexed_iter().filter(|&(_, e)| *e == Element::Start).map(|(pos, _)| pos);
+    let start = starts.next().ok_or_eyre("start never found")?;
+    if let Some((y, x)) = starts.next() {
+        return Err(eyre!(
+            "{}:{}: duplicate start found, original at coords {}, {}",
+            y + 1,
+            x + 1,
+            start.1,
+            start.0
+        ));
+    }
+    Ok((grid, start))
+}
+
+pub fn split_beam((start_y, start_x): (usize, usize), array: &ArrayRef2<Element>) -> Option<usize> {
+    let mut splits = 0usize;
+    if start_y + 1 >= array.nrows() || start_x >= array.ncols(){
+        return None;
+    }
+    let mut beams = HashSet::new();
+    beams.insert(start_x);
+    for row in array.axis_iter(Axis(0)).skip(start_y + 1) {
+        let splitters = row.indexed_iter().filter_map(|(x, elem)| if *elem == Element::Splitter {
+            Some(x)
+        } else {
+            None
+        });
+        for splitter in splitters {
+            if beams.contains(&splitter) {
+                beams.remove(&splitter);
+                if splitter > 0 {
+                   beams.insert(splitter - 1);
+                }
+                if splitter + 1 < array.ncols() {
+                    beams.insert(splitter + 1);
+                }
+                splits += 1;
+            }
+        }
+    }
+    Some(splits)
+}
+
+const TOO_MANY: &str = "too many timelines to count";
+
+pub fn timelines((start_y, start_x): (usize, usize), array: &ArrayRef2<Element>) -> Result<usize> {
+    if start_y + 1 >= array.nrows() || start_x >= array.ncols() {
+        return Err(eyre!("start is on the last row"));
+    }
+    let mut timelines = vec![0usize; array.ncols()];
+    timelines[start_x] = 1;
+    for row in array.axis_iter(Axis(0)).skip(start_y + 1) {
+        let splitters = row.indexed_iter().filter_map(|(x, elem)| if *elem == Element::Splitter {
+            Some(x)
+        } else {
+            None
+        });
+        for splitter in splitters {
+            if timelines[splitter] > 0 {
+                let count = timelines[splitter];
+                let sides = [splitter.checked_sub(1), Some(splitter + 1)];
+                for next in sides.into_iter().flatten() {
+                    if let Some(t) = timelines.get_mut(next) {
+                        *t = t.checked_add(count).ok_or_eyre(TOO_MANY)?;
+                    }
+                }
+                timelines[splitter] = 0;
+            }
+        }
+    }
+    timelines.into_iter().try_fold(0usize, usize::checked_add).ok_or_eyre(TOO_MANY)
+}
+
+
+#[derive(Debug)]
+pub struct Manifold {
+    pub grid: Grid<Element>,
+    pub start: (usize, usize),
+}
+
+pub fn parse(fname: &str, body: &str) -> Result<Manifold> {
+    let (grid, start) = parse_file(body).map_err(|e| eyre!("{fname}:{e}"))?;
+    Ok(Manifold { grid, start })
+}
+
+pub fn part1(manifold: &Manifold) -> Result<usize> {
+    split_beam(manifold.start, &manifold.grid).ok_or_eyre("start is on the last row")
+}
+
+pub fn part2(manifold: &Manifold) -> Result<usize> {
+    timelines(manifold.start, &manifold.grid)
+}
+
+/// Where a beam in `(y, x)` goes on the next row: straight down, or to both sides
+/// of a splitter there. Beams leave through the bottom and the sides.
+fn beam_steps(grid: &Grid<Element>, (y, x): (usize, usize)) -> Vec<(usize, usize)> {
+    match grid.get((y + 1, x)) {
+        None => Vec::new(),
+        Some(Element::Splitter) => [x.checked_sub(1), Some(x + 1)]
+            .into_iter()
+            .flatten()
+            .filter(|&x| x < grid.ncols())
+            .map(|x| (y + 1, x))
+            .collect(),
+        Some(_) => vec![(y + 1, x)],
+    }
+}
+
+/// Part 1 by moving every beam down a row at a time, collecting the splitters hit.
+pub fn splits_by_rows(Manifold { grid, start }: &Manifold) -> Result<usize> {
+    if start.0 + 1 >= grid.nrows() {
+        return Err(eyre!("start is on the last row"));
+    }
+    let mut beams = HashSet::from([*start]);
+    let mut hit = HashSet::new();
+    while !beams.is_empty() {
+        let mut next = HashSet::new();
+        for &(y, x) in &beams {
+            if grid.get((y + 1, x)) == Some(&Element::Splitter) {
+                hit.insert((y + 1, x));
+            }
+            next.extend(beam_steps(grid, (y, x)));
+        }
+        beams = next;
+    }
+    Ok(hit.len())
+}
+
+/// Most timelines [`timelines_one_by_one`] will follow.
+pub const MAX_REFERENCE_TIMELINES: usize = 1_000_000;
+
+/// Part 2 by following each timeline down to the bottom row on its own, or `None`
+/// if there are more than [`MAX_REFERENCE_TIMELINES`].
+pub fn timelines_one_by_one(
+    Manifold { grid, start }: &Manifold,
+) -> Option<Result<usize>> {
+    if start.0 + 1 >= grid.nrows() {
+        return Some(Err(eyre!("start is on the last row")));
+    }
+    let mut beams = vec![*start];
+    let mut count = 0;
+    while let Some(beam) = beams.pop() {
+        if beam.0 + 1 == grid.nrows() {
+            count += 1;
+            if count > MAX_REFERENCE_TIMELINES {
+                return None;
+            }
+        }
+        beams.extend(beam_steps(grid, beam));
+    }
+    Some(Ok(count))
+}
+
+/// A manifold `size` columns wide with the start centred on the top row.
+///
+/// Splitters sit on every other row, never next to each other, and there are at
+/// most 60 rows of them so the number of timelines fits in 64 bits.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let cols = size.max(3);
+    let rows = 2 * cols.min(60) + 2;
+    let centre = cols / 2;
+    let grid = Array2::from_shape_fn((rows, cols), |(y, x)| {
+        if (y, x) == (0, centre) {
+            Element::Start
+        } else if y % 2 == 0
+            && y > 0
+            && x > 0
+            && x + 1 < cols
+            && (x + y / 2).abs_diff(centre) % 2 == 1
+            && rng.chance(0.5)
+        {
+            Element::Splitter
+        } else {
+            Element::Empty
+        }
+    });
+    Grid::from(grid).to_string()
+}
+
+pub struct Tachyon;
+
+impl Solution for Tachyon {
+    type Input<'a> = Manifold;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(manifold: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part1(manifold)?.into())
+    }
+
+    fn part2(manifold: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part2(manifold)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part1(manifold: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(splits_by_rows(manifold).map(Answer::from))
+    }
+
+    fn reference_part2(manifold: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(timelines_one_by_one(manifold)?.map(Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day07-example.txt");
+
+    #[test]
+    fn example() {
+        let manifold = parse("example", EXAMPLE).unwrap();
+        assert_eq!(manifold.start, (0, 7));
+        assert_eq!(part1(&manifold).unwrap(), 21);
+        assert_eq!(part2(&manifold).unwrap(), 40);
+    }
+
+    #[test]
+    fn references_match_example() {
+        let manifold = parse("example", EXAMPLE).unwrap();
+        assert_eq!(splits_by_rows(&manifold).unwrap(), 21);
+        assert_eq!(timelines_one_by_one(&manifold).unwrap().unwrap(), 40);
+    }
+
+    #[test]
+    fn renders_back_to_input() {
+        let manifold = parse("example", EXAMPLE).unwrap();
+        assert_eq!(manifold.grid.to_string(), EXAMPLE);
+    }
+
+    #[test]
+    fn start_on_last_row() {
+        let manifold = parse("last", "...\n.S.\n").unwrap();
+        assert!(part1(&manifold).is_err());
+        assert!(part2(&manifold).is_err());
+    }
+
+    #[test]
+    fn too_many_timelines() {
+        let mut body = format!("{0}S{0}\n", ".".repeat(100));
+        for _ in 0..100 {
+            body += &format!("{}\n", "^".repeat(201));
+        }
+        let manifold = parse("many", &body).unwrap();
+        assert!(part2(&manifold).is_err());
+    }
+
+    #[test]
+    fn duplicate_start() {
+        assert!(parse("duplicate", "S.S\n...\n").is_err());
+    }
+
+    #[test]
+    fn error_position() {
+        let e = parse("bad", "..S..\n..x..\n").unwrap_err();
+        assert_eq!(e.to_string(), "bad:2:3: unknown element: x");
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::{HashMap, HashSet};
+
+use common::{Answer, Rng, Solution};
+use eyre::{OptionExt, Result};
+use itertools::Itertools;
+use nom::character::complete::{char, digit1, newline};
+use nom::combinator::{all_consuming, map, map_res, opt, verify};
+use nom::multi::separated_list1;
+use nom::sequence::terminated;
+use nom::{IResult, Parser};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3D(pub isize, pub isize, pub isize);
+
+/// Coordinates must stay below this, so squared distances between points fit in an `isize`.
+pub const MAX_COORD: isize = 1 << 30;
+
+pub fn parse_number(input: &str) -> IResult<&str, isize> {
+    let number = map_res(digit1, |s: &str| s.parse::<isize>());
+    verify(number, |n| *n < MAX_COORD).parse(input)
+}
+
+impl Point3D {
+    pub fn distance(&self, rhs: &Point3D) -> f64 {
+        let x = self.0 - rhs.0;
+        let y = self.1 - rhs.1;
+        let z = self.2 - rhs.2;
+        ((x * x + y * y + z * z) as f64).sqrt()
+    }
+
+    /// The square of [`Point3D::distance`], exactly.
+    pub fn squared_distance(&self, rhs: &Point3D) -> isize {
+        (self.0 - rhs.0).pow(2) + (self.1 - rhs.1).pow(2) + (self.2 - rhs.2).pow(2)
+    }
+}
+
+
+pub fn parse_point(input: &str) -> IResult<&str, Point3D> {
+    map((
+        terminated(parse_number, char(',')),
+        terminated(parse_number, char(',')),
+        parse_number
+    ), |(x, y, z)| Point3D(x, y, z)).parse(input)
+}
+
+pub fn parse_file(input: &str) -> IResult<&str, Vec<Point3D>> {
+    all_consuming(terminated(separated_list1(newline, parse_point), opt(newline))).parse(input)
+}
+
+pub fn to_circuits(pairs: impl IntoIterator<Item = (Point3D, Point3D)>, n: usize) -> HashMap<usize, Vec<Point3D>> {
+    let mut id = 0usize;
+    let mut circuits = HashMap::new();
+    let mut points = HashMap::new();
+    for (lhs, rhs) in pairs.into_iter().take(n) {
+        match (points.get(&lhs), points.get(&rhs)) {
+            (None, None) => {
+                circuits.insert(id, vec![lhs.clone(), rhs.clone()]);
+                points.insert(lhs.clone(), id);
+                points.insert(rhs.clone(), id);
+                id += 1;
+            },
+            (Some(id), None) => {
+                circuits.get_mut(id).unwrap().push(rhs.clone());
+                points.insert(rhs.clone(), *id);
+            },
+            (None, Some(id)) => {
+                circuits.get_mut(id).unwrap().push(lhs.clone());
+                points.insert(lhs.clone(), *id);
+            },
+            (Some(&lhs_id), Some(&rhs_id)) => {
+                if lhs_id != rhs_id {
+                    let mut removed = circuits.remove(&rhs_id).unwrap();
+                    for point in removed.iter() {
+                        *points.get_mut(point).unwrap() = lhs_id;
+                    }
+                    circuits.get_mut(&lhs_id).unwrap().append(&mut removed);
+                }
+            }
+        }
+    }
+    circuits
+}
+
+pub fn until_connected(pairs: impl IntoIterator<Item =(Point3D, Point3D)>, num_points: usize) -> Option<(Point3D, Point3D)>
+{
+    let mut id = 0usize;
+    let mut circuits = HashMap::new();
+    let mut points = HashMap::new();
+    for (lhs, rhs) in pairs.into_iter() {
+        match (points.get(&lhs), points.get(&rhs)) {
+            (None, None) => {
+                if num_points == 2 {
+                    return Some((lhs, rhs));
+                }
+                circuits.insert(id, vec![lhs.clone(), rhs.clone()]);
+                points.insert(lhs.clone(), id);
+                points.insert(rhs.clone(), id);
+                id += 1;
+            },
+            (Some(id), None) => {
+                let dest = circuits.get_mut(id).unwrap();
+                dest.push(rhs.clone());
+                if dest.len() == num_points {
+                    return Some((lhs, rhs));
+                }
+                points.insert(rhs.clone(), *id);
+            },
+            (None, Some(id)) => {
+                let dest = circuits.get_mut(id).unwrap();
+                dest.push(lhs.clone());
+                if dest.len() == num_points {
+                    return Some((lhs, rhs));
+                }
+                points.insert(lhs.clone(), *id);
+            },
+            (Some(&lhs_id), Some(&rhs_id)) => {
+                if lhs_id != rhs_id {
+                    let mut removed = circuits.remove(&rhs_id).unwrap();
+                    for point in removed.iter() {
+                        *points.get_mut(point).unwrap() = lhs_id;
+                    }
+                    let dest = circuits.get_mut(&lhs_id).unwrap();
+                    dest.append(&mut removed);
+                    if dest.len() == num_points {
+                        return Some((lhs, rhs));
+                    }
+                    circuits.get_mut(&lhs_id).unwrap().append(&mut removed);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Number of shortest connections made for part 1.
+pub const CONNECTIONS: usize = 1000;
+
+pub fn parse(fname: &str, body: &str) -> Result<Vec<Point3D>> {
+    common::finish(fname, body, parse_file(body))
+}
+
+/// Every pair of indices into `points`, closest first and equally close pairs in
+/// index order.
+fn closest_pairs(points: &[Point3D]) -> Vec<(usize, usize)> {
+    let mut pairs = (0..points.len()).tuple_combinations().collect::<Vec<_>>();
+    pairs
+        .sort_unstable_by_key(|&(i, j)| (points[i].squared_distance(&points[j]), i, j));
+    pairs
+}
+
+pub fn sorted_pairs(points: &[Point3D]) -> Vec<(Point3D, Point3D)> {
+    let pairs = closest_pairs(points).into_iter();
+    pairs.map(|(i, j)| (points[i].clone(), points[j].clone())).collect()
+}
+
+/// Product of the sizes of the three largest circuits after `connections` joins.
+pub fn largest_circuits(points: &[Point3D], connections: usize) -> usize {
+    let circuits = to_circuits(sorted_pairs(points), connections);
+    let lengths = circuits.values().map(|v| v.len()).sorted_unstable();
+    lengths.rev().take(3).product::<usize>()
+}
+
+/// Most junction boxes [`connect_by_labels`] will connect.
+pub const MAX_REFERENCE_POINTS: usize = 200;
+
+/// Both parts by joining pairs in order of their exact squared distance, giving
+/// every box in the absorbed circuit the label of the other one.
+///
+/// Returns the product of the three largest circuits after `connections` joins and
+/// the product of the X coordinates of the pair that connects everything, or `None`
+/// if there are more than [`MAX_REFERENCE_POINTS`] boxes.
+pub fn connect_by_labels(
+    points: &[Point3D],
+    connections: usize,
+) -> Option<(usize, Option<isize>)> {
+    if points.len() > MAX_REFERENCE_POINTS {
+        return None;
+    }
+    let pairs = closest_pairs(points);
+    let largest = |labels: &[usize]| {
+        let sizes = labels.iter().counts().into_values().sorted_unstable();
+        sizes.rev().take(3).product::<usize>()
+    };
+    let mut labels = (0..points.len()).collect::<Vec<_>>();
+    let mut after_connections = None;
+    let mut last = None;
+    for (n, &(i, j)) in pairs.iter().enumerate() {
+        if n == connections {
+            after_connections = Some(largest(&labels));
+        }
+        let (kept, absorbed) = (labels[i], labels[j]);
+        for label in labels.iter_mut().filter(|l| **l == absorbed) {
+            *label = kept;
+        }
+        if last.is_none() && labels.iter().all(|&l| l == kept) {
+            last = Some(points[i].0 * points[j].0);
+        }
+    }
+    Some((after_connections.unwrap_or_else(|| largest(&labels)), last))
+}
+
+pub fn part1(points: &[Point3D]) -> usize {
+    largest_circuits(points, CONNECTIONS)
+}
+
+pub fn part2(points: &[Point3D]) -> Result<isize> {
+    let (lhs, rhs) = until_connected(sorted_pairs(points), points.len())
+        .ok_or_eyre("search failed")?;
+    Ok(lhs.0 * rhs.0)
+}
+
+/// `size` distinct junction boxes, at least two, with coordinates below 100,000.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut points = HashSet::new();
+    while points.len() < size.max(2) {
+        let mut coord = || rng.range(0isize..=99_999);
+        points.insert(Point3D(coord(), coord(), coord()));
+    }
+    let mut points = points.into_iter().collect::<Vec<_>>();
+    points.sort_unstable();
+    rng.shuffle(&mut points);
+    points.iter().map(|Point3D(x, y, z)| format!("{x},{y},{z}\n")).collect()
+}
+
+pub struct Playground;
+
+impl Solution for Playground {
+    type Input<'a> = Vec<Point3D>;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(points: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part1(points).into())
+    }
+
+    fn part2(points: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part2(points)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part1(points: &Self::Input<'_>) -> Option<Result<Answer>> {
+        let (largest, _) = connect_by_labels(points, CONNECTIONS)?;
+        Some(Ok(largest.into()))
+    }
+
+    fn reference_part2(points: &Self::Input<'_>) -> Option<Result<Answer>> {
+        let (_, last) = connect_by_labels(points, CONNECTIONS)?;
+        Some(last.map(Answer::from).ok_or_eyre("search failed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day08-example.txt");
+
+    #[test]
+    fn example() {
+        let points = parse("example", EXAMPLE).unwrap();
+        // The puzzle makes ten connections for the example instead of CONNECTIONS.
+        assert_eq!(largest_circuits(&points, 10), 40);
+        assert_eq!(part2(&points).unwrap(), 25272);
+    }
+
+    #[test]
+    fn labels_match_example() {
+        let points = parse("example", EXAMPLE).unwrap();
+        assert_eq!(connect_by_labels(&points, 10), Some((40, Some(25272))));
+    }
+
+    #[test]
+    fn two_boxes() {
+        let points = parse("two", "1,2,3\n4,5,6\n").unwrap();
+        assert_eq!(part2(&points).unwrap(), 4);
+        assert!(part2(&points[..1]).is_err());
+    }
+
+    #[test]
+    fn equally_close_pairs() {
+        // 0-1 and 1-2 are as close as each other; both paths join 0-1 first.
+        let points = parse("ties", "0,0,0\n1,0,0\n2,0,0\n").unwrap();
+        assert_eq!(part2(&points).unwrap(), 2);
+        assert_eq!(connect_by_labels(&points, 1), Some((2, Some(2))));
+    }
+
+    #[test]
+    fn coordinate_too_large() {
+        assert!(parse("large", "0,0,1073741823\n1,1,1\n").is_ok());
+        assert!(parse("large", "0,0,1073741824\n1,1,1\n").is_err());
+    }
+}
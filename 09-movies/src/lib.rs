@@ -0,0 +1,319 @@
+use std::cmp;
+
+use common::{Answer, Rng, Solution};
+use eyre::{OptionExt, Result};
+use itertools::Itertools;
+use nom::character::complete::{char, digit1, newline};
+use nom::combinator::{all_consuming, map, map_res, opt, verify};
+use nom::multi::separated_list1;
+use nom::sequence::terminated;
+use nom::{IResult, Parser};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2D(pub isize, pub isize);
+
+impl Point2D {
+    pub fn rect_area(&self, rhs: &Point2D) -> isize {
+        let x = (self.0 - rhs.0).abs() + 1;
+        let y = (self.1 - rhs.1).abs() + 1;
+        x * y
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Line {
+    pub start: Point2D,
+    pub end: Point2D
+}
+
+impl Line {
+    fn left(&self) -> isize {
+        cmp::min(self.start.0, self.end.0)
+    }
+
+    fn right(&self) -> isize {
+        cmp::max(self.start.0, self.end.0)
+    }
+
+    fn top(&self) -> isize {
+        cmp::min(self.start.1, self.end.1)
+    }
+
+    fn bottom(&self) -> isize {
+        cmp::max(self.start.1, self.end.1)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub ul: Point2D,
+    pub br: Point2D
+}
+
+impl Rect {
+    fn left(&self) -> isize {
+        self.ul.0
+    }
+
+    fn right(&self) -> isize {
+        self.br.0
+    }
+
+    fn top(&self) -> isize {
+        self.ul.1
+    }
+
+    fn bottom(&self) -> isize {
+        self.br.1
+    }
+
+    pub fn intersected_by(&self, rhs: &Line) -> bool {
+        self.left() < rhs.right() && self.right() > rhs.left() && self.top() < rhs.bottom() && self.bottom() > rhs.top()
+    }
+
+    /// The centre in half-tile units, so that it stays on whole numbers.
+    fn doubled_centre(&self) -> (isize, isize) {
+        (self.ul.0 + self.br.0, self.ul.1 + self.br.1)
+    }
+}
+
+/// Whether a point, given in half-tile units, lies inside the loop of `lines` or on
+/// it.
+fn encloses(lines: &[Line], (x, y): (isize, isize)) -> bool {
+    let on_loop = lines.iter().any(|l| {
+        (2 * l.left()..=2 * l.right()).contains(&x)
+            && (2 * l.top()..=2 * l.bottom()).contains(&y)
+    });
+    // Cast a ray to the right, counting the vertical lines it crosses by their top
+    // end but not their bottom one so that passing a corner counts once.
+    let crossings = lines.iter().filter(|l| {
+        let (top, bottom) = (2 * l.top(), 2 * l.bottom());
+        l.left() == l.right() && 2 * l.left() > x && top <= y && y < bottom
+    });
+    on_loop || crossings.count() % 2 == 1
+}
+
+/// Coordinates must stay below this, so the area of any rectangle between them fits in an `isize`.
+pub const MAX_COORD: isize = 1 << 31;
+
+pub fn parse_number(input: &str) -> IResult<&str, isize> {
+    let number = map_res(digit1, |s: &str| s.parse::<isize>());
+    verify(number, |n| *n < MAX_COORD).parse(input)
+}
+
+pub fn parse_point(input: &str) -> IResult<&str, Point2D> {
+    map((
+        terminated(parse_number, char(',')),
+        parse_number
+    ), |(x, y)| Point2D(x, y)).parse(input)
+}
+
+pub fn parse_file(input: &str) -> IResult<&str, Vec<Point2D>> {
+    all_consuming(terminated(separated_list1(newline, parse_point), opt(newline))).parse(input)
+}
+
+
+pub fn parse(fname: &str, body: &str) -> Result<Vec<Point2D>> {
+    common::finish(fname, body, parse_file(body))
+}
+
+/// Every pair of points with the area of the rectangle they span, smallest first.
+fn sorted_areas(points: &[Point2D]) -> Vec<(isize, &Point2D, &Point2D)> {
+    let mut areas = points.iter().combinations(2).map(|v| (v[0].rect_area(v[1]), v[0], v[1])).collect::<Vec<_>>();
+    areas.sort_unstable();
+    areas
+}
+
+pub fn part1(points: &[Point2D]) -> Result<isize> {
+    let areas = sorted_areas(points);
+    areas.last().map(|(a, _, _)| *a).ok_or_eyre("too few points")
+}
+
+pub fn part2(points: &[Point2D]) -> Result<isize> {
+    let areas = sorted_areas(points);
+    let lines = points.iter().circular_tuple_windows().map(|(l, r)| if l <= r {
+        Line{start: l.clone(), end: r.clone()}
+    } else {
+        Line{start: r.clone(), end: l.clone()}
+    }).collect::<Vec<_>>();
+    areas.iter().rev().find(|(_, p1, p2)| {
+        let p3 = Point2D(p1.0, p2.1);
+        let p4 = Point2D(p2.0, p1.1);
+        let mut points = [p1, p2, &p3, &p4];
+        points.sort_unstable();
+        let rect = Rect { ul: points[0].clone(), br: points[3].clone()};
+        // With no line through it, the rectangle is either all inside or all outside.
+        lines.iter().all(|l| !rect.intersected_by(l))
+            && encloses(&lines, rect.doubled_centre())
+    }).map(|(a, _, _)| *a).ok_or_eyre("nothing inside")
+}
+
+/// Most red tiles [`largest_by_flood_fill`] will look at.
+pub const MAX_REFERENCE_POINTS: usize = 200;
+
+/// Splits the axis at every coordinate in `coords` into runs of tiles that are
+/// either one of them or strictly between two of them, with an empty run before
+/// and after, and returns the start of each run.
+fn runs(coords: impl Iterator<Item = isize>) -> Vec<isize> {
+    let coords = coords.sorted_unstable().dedup().collect::<Vec<_>>();
+    let mut starts = vec![isize::MIN];
+    for (i, &c) in coords.iter().enumerate() {
+        starts.push(c);
+        if coords.get(i + 1).is_none_or(|&next| next > c + 1) {
+            starts.push(c + 1);
+        }
+    }
+    starts
+}
+
+/// Part 2 on a grid of runs of tiles: draws the loop, floods everything reachable
+/// from outside it and takes the largest rectangle whose runs are all left dry.
+///
+/// Returns `None` if there are more than [`MAX_REFERENCE_POINTS`] red tiles.
+pub fn largest_by_flood_fill(points: &[Point2D]) -> Option<Result<isize>> {
+    if points.len() > MAX_REFERENCE_POINTS {
+        return None;
+    }
+    let xs = runs(points.iter().map(|p| p.0));
+    let ys = runs(points.iter().map(|p| p.1));
+    let run = |starts: &[isize], v: isize| starts.partition_point(|&s| s <= v) - 1;
+    let cell = |p: &Point2D| (run(&ys, p.1), run(&xs, p.0));
+    let mut inside = vec![vec![true; xs.len()]; ys.len()];
+    let mut on_loop = vec![vec![false; xs.len()]; ys.len()];
+    for (a, b) in points.iter().circular_tuple_windows() {
+        let ((y0, x0), (y1, x1)) = (cell(a), cell(b));
+        for row in &mut on_loop[y0.min(y1)..=y0.max(y1)] {
+            row[x0.min(x1)..=x0.max(x1)].fill(true);
+        }
+    }
+    let mut flood = vec![(0, 0)];
+    while let Some((y, x)) = flood.pop() {
+        if on_loop[y][x] || !inside[y][x] {
+            continue;
+        }
+        inside[y][x] = false;
+        flood.extend(
+            [(y.wrapping_sub(1), x), (y + 1, x), (y, x.wrapping_sub(1)), (y, x + 1)]
+                .into_iter()
+                .filter(|&(y, x)| y < ys.len() && x < xs.len()),
+        );
+    }
+    let largest = sorted_areas(points).into_iter().rev().find(|(_, a, b)| {
+        let ((y0, x0), (y1, x1)) = (cell(a), cell(b));
+        (y0.min(y1)..=y0.max(y1))
+            .all(|y| (x0.min(x1)..=x0.max(x1)).all(|x| inside[y][x]))
+    });
+    Some(largest.map(|(area, _, _)| area).ok_or_eyre("nothing inside"))
+}
+
+/// The corners of a rectilinear polygon with `size` steps along its top edge, so
+/// `2 * size + 2` red tiles.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut x = rng.range(0isize..=1000);
+    let mut height = 0isize;
+    let mut points = vec![Point2D(x, 0)];
+    for _ in 0..size.max(1) {
+        let mut next = height;
+        while next == height {
+            next = rng.range(1..=100_000);
+        }
+        height = next;
+        points.push(Point2D(x, height));
+        x += rng.range(1..=1000);
+        points.push(Point2D(x, height));
+    }
+    points.push(Point2D(x, 0));
+    if rng.chance(0.5) {
+        points.reverse();
+    }
+    points.iter().map(|Point2D(x, y)| format!("{x},{y}\n")).collect()
+}
+
+pub struct Movies;
+
+impl Solution for Movies {
+    type Input<'a> = Vec<Point2D>;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(points: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part1(points)?.into())
+    }
+
+    fn part2(points: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part2(points)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part2(points: &Self::Input<'_>) -> Option<Result<Answer>> {
+        Some(largest_by_flood_fill(points)?.map(Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day09-example.txt");
+
+    #[test]
+    fn example() {
+        let points = parse("example", EXAMPLE).unwrap();
+        assert_eq!(part1(&points).unwrap(), 50);
+        assert_eq!(part2(&points).unwrap(), 24);
+    }
+
+    #[test]
+    fn flood_fill_matches_example() {
+        let points = parse("example", EXAMPLE).unwrap();
+        assert_eq!(largest_by_flood_fill(&points).unwrap().unwrap(), 24);
+    }
+
+    #[test]
+    fn rectangle_outside_notch() {
+        let points =
+            parse("notch", "0,0\n10,0\n10,10\n9,10\n9,1\n1,1\n1,10\n0,10\n").unwrap();
+        assert_eq!(part2(&points).unwrap(), 22);
+        assert_eq!(largest_by_flood_fill(&points).unwrap().unwrap(), 22);
+    }
+
+    fn rect() -> Rect {
+        Rect { ul: Point2D(2, 3), br: Point2D(9, 5) }
+    }
+
+    fn line(start: (isize, isize), end: (isize, isize)) -> Line {
+        Line { start: Point2D(start.0, start.1), end: Point2D(end.0, end.1) }
+    }
+
+    #[test]
+    fn intersected_through_interior() {
+        assert!(rect().intersected_by(&line((5, 0), (5, 9))));
+        assert!(rect().intersected_by(&line((0, 4), (12, 4))));
+        assert!(rect().intersected_by(&line((5, 4), (5, 9))));
+    }
+
+    #[test]
+    fn not_intersected_along_edges() {
+        assert!(!rect().intersected_by(&line((2, 0), (2, 9))));
+        assert!(!rect().intersected_by(&line((0, 5), (12, 5))));
+        assert!(!rect().intersected_by(&line((9, 3), (9, 5))));
+    }
+
+    #[test]
+    fn not_intersected_outside() {
+        assert!(!rect().intersected_by(&line((0, 0), (12, 0))));
+        assert!(!rect().intersected_by(&line((11, 0), (11, 9))));
+        assert!(!rect().intersected_by(&line((5, 0), (5, 3))));
+    }
+
+    #[test]
+    fn coordinate_too_large() {
+        assert!(parse("large", "0,2147483647\n1,1\n").is_ok());
+        assert!(parse("large", "0,2147483648\n1,1\n").is_err());
+    }
+}
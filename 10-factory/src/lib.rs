@@ -0,0 +1,501 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use common::{Answer, Rng, Solution};
+use eyre::{OptionExt, eyre};
+use itertools::Itertools;
+use nom::bytes::complete::is_a;
+use nom::character::complete::{char, digit1, newline, space1};
+use nom::combinator::{all_consuming, map, map_res, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, terminated};
+use nom::{IResult, Parser};
+
+#[derive(Debug)]
+pub struct Machine {
+    indicators: Vec<bool>,
+    buttons: Vec<Vec<u16>>,
+    joltages: Vec<u16>,
+}
+
+impl Machine {
+    pub fn new(
+        indicators: Vec<bool>,
+        buttons: Vec<Vec<u16>>,
+        joltages: Vec<u16>,
+    ) -> eyre::Result<Self> {
+        if joltages.len() != indicators.len() {
+            return Err(eyre!(
+                "{} joltages for {} indicators",
+                joltages.len(),
+                indicators.len()
+            ));
+        }
+        if buttons.iter().any(|b| !b.iter().all_unique()) {
+            return Err(eyre!("button lists an indicator more than once"));
+        }
+        if buttons.iter().flatten().all(|i| usize::from(*i) < indicators.len()) {
+            Ok(Self { indicators, buttons, joltages })
+        } else {
+            Err(eyre!("button has too large indicator index"))
+        }
+    }
+
+    pub fn num_buttons(&self) -> usize {
+        self.buttons.len()
+    }
+}
+
+pub fn parse_number<N: std::str::FromStr>(input: &str) -> IResult<&str, N> {
+    map_res(digit1, |s: &str| s.parse::<N>()).parse(input)
+}
+
+pub fn parse_indicators(input: &str) -> IResult<&str, Vec<bool>> {
+    map(delimited(char('['), is_a("#."), char(']')), |s: &str| {
+        s.chars()
+            .map(|c| match c {
+                '#' => true,
+                '.' => false,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>()
+    })
+    .parse(input)
+}
+
+pub fn parse_button(input: &str) -> IResult<&str, Vec<u16>> {
+    delimited(char('('), separated_list1(char(','), parse_number), char(')'))
+        .parse(input)
+}
+
+pub fn parse_joltages(input: &str) -> IResult<&str, Vec<u16>> {
+    delimited(char('{'), separated_list1(char(','), parse_number), char('}'))
+        .parse(input)
+}
+
+pub fn parse_machine(input: &str) -> IResult<&str, Machine> {
+    let parser = (
+        terminated(parse_indicators, space1),
+        terminated(separated_list1(space1, parse_button), space1),
+        parse_joltages,
+    );
+    map_res(parser, |(i, b, j)| Machine::new(i, b, j)).parse(input)
+}
+
+pub fn parse_file(input: &str) -> IResult<&str, Vec<Machine>> {
+    all_consuming(terminated(separated_list1(newline, parse_machine), opt(newline)))
+        .parse(input)
+}
+
+struct ButtonStarter<'a> {
+    goal: &'a [bool],
+    buttons: &'a [Vec<u16>],
+    indicators: Vec<bool>,
+    counts: Vec<u16>,
+}
+
+impl<'a> ButtonStarter<'a> {
+    fn indicators_ok(&self) -> bool {
+        self.indicators == self.goal
+    }
+
+    pub fn num_buttons(&self) -> usize {
+        self.buttons.len()
+    }
+
+    fn press_button(&mut self, index: usize) {
+        let button = &self.buttons[index];
+        for indicator in button {
+            let indicator = usize::from(*indicator);
+            self.indicators[indicator] = !self.indicators[indicator];
+            self.counts[indicator] += 1;
+        }
+    }
+
+    fn into_state(self) -> (Vec<bool>, Vec<u16>) {
+        (self.indicators, self.counts)
+    }
+}
+
+impl<'a> std::fmt::Debug for ButtonStarter<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ButtonStarter")
+            .field("goal", &self.goal)
+            .field("indicators", &self.indicators)
+            .field("counts", &self.counts)
+            .finish()
+    }
+}
+
+impl<'a> From<&'a Machine> for ButtonStarter<'a> {
+    fn from(machine: &'a Machine) -> Self {
+        let goal = &machine.indicators;
+        let goal_len = goal.len();
+        Self {
+            goal,
+            buttons: &machine.buttons,
+            indicators: vec![false; goal_len],
+            counts: vec![0; goal_len],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct JoltageStarter {
+    joltages: Vec<u16>,
+}
+
+impl JoltageStarter {
+    fn as_indicators(&self) -> impl Iterator<Item = bool> {
+        self.joltages.iter().map(|j| *j % 2 == 1)
+    }
+
+    fn halve_joltages(&mut self) {
+        for j in &mut self.joltages {
+            *j /= 2;
+        }
+    }
+
+    fn count_down(&mut self, counts: &[u16]) -> Option<bool> {
+        for (j, c) in self.joltages.iter_mut().zip(counts.iter()) {
+            *j = j.checked_sub(*c)?;
+        }
+        Some(self.joltages.iter().all(|j| *j == 0))
+    }
+}
+
+impl From<&Machine> for JoltageStarter {
+    fn from(machine: &Machine) -> Self {
+        let joltages = machine.joltages.clone();
+        Self { joltages }
+    }
+}
+
+pub fn shortest_buttons(machine: &Machine) -> Option<usize> {
+    (1..=machine.num_buttons())
+        .flat_map(|n| (0..machine.num_buttons()).combinations(n))
+        .filter_map(|indices| {
+            let mut starter = ButtonStarter::from(machine);
+            for index in &indices {
+                starter.press_button(*index);
+            }
+            if starter.indicators_ok() { Some(indices.len()) } else { None }
+        })
+        .next()
+}
+
+type IndicatorsMap = HashMap<Vec<bool>, Vec<(usize, Vec<u16>)>>;
+fn all_indicators(machine: &Machine) -> IndicatorsMap {
+    let end = machine.num_buttons();
+    let mut map = (1..=end).flat_map(|n| (0..end).combinations(n)).fold(
+        HashMap::new(),
+        |mut map: IndicatorsMap, indices| {
+            let mut starter = ButtonStarter::from(machine);
+            for index in &indices {
+                starter.press_button(*index % starter.num_buttons());
+            }
+            let (key, counts) = starter.into_state();
+            map.entry(key).or_default().push((indices.len(), counts));
+            map
+        },
+    );
+    map.entry(vec![false; machine.indicators.len()])
+        .or_default()
+        .push((0, vec![0; machine.indicators.len()]));
+    map
+}
+
+pub fn shortest_joltages(machine: &Machine) -> Option<usize> {
+    let indicators = all_indicators(machine);
+
+    fn inner(starter: &JoltageStarter, lookup: &IndicatorsMap) -> Option<usize> {
+        let indicators = starter.as_indicators().collect::<Vec<_>>();
+        lookup
+            .get(&indicators)
+            .iter()
+            .copied()
+            .flatten()
+            .filter_map(|(clicks, counts)| {
+                let mut starter = starter.clone();
+                if starter.count_down(counts)? {
+                    Some(*clicks)
+                } else {
+                    starter.halve_joltages();
+                    inner(&starter, lookup).map(|c| 2 * c + clicks)
+                }
+            })
+            .min()
+    }
+
+    let starter = machine.into();
+    inner(&starter, &indicators)
+}
+
+/// [`shortest_buttons`] by a breadth-first search over the states of the lights.
+pub fn buttons_by_search(machine: &Machine) -> Option<usize> {
+    let mut seen = HashSet::from([vec![false; machine.indicators.len()]]);
+    let mut queue = VecDeque::from([(vec![false; machine.indicators.len()], 0)]);
+    while let Some((lights, presses)) = queue.pop_front() {
+        if lights == machine.indicators {
+            return Some(presses);
+        }
+        for button in &machine.buttons {
+            let mut next = lights.clone();
+            for &i in button {
+                next[usize::from(i)] ^= true;
+            }
+            if seen.insert(next.clone()) {
+                queue.push_back((next, presses + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Most steps [`joltages_by_search`] will take for one machine.
+pub const MAX_REFERENCE_STEPS: usize = 1_000_000;
+
+/// State of [`joltages_by_search`].
+struct JoltageSearch<'a> {
+    buttons: &'a [Vec<u16>],
+    /// Buttons whose number of presses has not been decided yet.
+    free: Vec<bool>,
+    remaining: Vec<u16>,
+    presses: usize,
+    best: Option<usize>,
+    steps: usize,
+}
+
+impl JoltageSearch<'_> {
+    /// Most times `button` can still be pressed without overshooting a joltage.
+    fn room(&self, button: usize) -> u16 {
+        let lights =
+            self.buttons[button].iter().map(|&i| self.remaining[usize::from(i)]);
+        lights.min().unwrap_or(0)
+    }
+
+    fn press(&mut self, button: usize, times: u16) {
+        for &i in &self.buttons[button] {
+            self.remaining[usize::from(i)] -= times;
+        }
+        self.presses += usize::from(times);
+    }
+
+    fn unpress(&mut self, button: usize, times: u16) {
+        for &i in &self.buttons[button] {
+            self.remaining[usize::from(i)] += times;
+        }
+        self.presses -= usize::from(times);
+    }
+
+    /// Decides every free button that reaches the light with the fewest of them,
+    /// trying each way to share out that light's remaining joltage.
+    fn search(&mut self) -> Option<()> {
+        self.steps += 1;
+        if self.steps > MAX_REFERENCE_STEPS {
+            return None;
+        }
+        let most = usize::from(self.remaining.iter().copied().max().unwrap_or(0));
+        if self.best.is_some_and(|best| self.presses + most >= best) {
+            return Some(());
+        }
+        if most == 0 {
+            self.best = Some(self.presses);
+            return Some(());
+        }
+        let reaching = |light: usize| {
+            (0..self.buttons.len())
+                .filter(|&b| self.free[b] && self.buttons[b].contains(&(light as u16)))
+                .collect::<Vec<_>>()
+        };
+        let light = (0..self.remaining.len())
+            .filter(|&l| self.remaining[l] > 0)
+            .min_by_key(|&l| reaching(l).len())?;
+        let buttons = reaching(light);
+        for &b in &buttons {
+            self.free[b] = false;
+        }
+        let result = self.share(&buttons, self.remaining[light]);
+        for &b in &buttons {
+            self.free[b] = true;
+        }
+        result
+    }
+
+    /// Presses `buttons` a total of `joltage` times in every possible way, searching
+    /// on from each.
+    fn share(&mut self, buttons: &[usize], joltage: u16) -> Option<()> {
+        let Some((&first, rest)) = buttons.split_first() else {
+            return Some(());
+        };
+        let most = joltage.min(self.room(first));
+        let tries = if rest.is_empty() { joltage..=most } else { 0..=most };
+        for times in tries {
+            self.press(first, times);
+            let result = if rest.is_empty() {
+                self.search()
+            } else {
+                self.share(rest, joltage - times)
+            };
+            self.unpress(first, times);
+            result?;
+        }
+        Some(())
+    }
+}
+
+/// [`shortest_joltages`] by an exhaustive search over how often to press each
+/// button, or `None` if it takes more than [`MAX_REFERENCE_STEPS`] steps.
+pub fn joltages_by_search(machine: &Machine) -> Option<Option<usize>> {
+    let mut search = JoltageSearch {
+        buttons: &machine.buttons,
+        free: vec![true; machine.buttons.len()],
+        remaining: machine.joltages.clone(),
+        presses: 0,
+        best: None,
+        steps: 0,
+    };
+    search.search()?;
+    Some(search.best)
+}
+
+pub fn parse(fname: &str, body: &str) -> eyre::Result<Vec<Machine>> {
+    common::finish(fname, body, parse_file(body))
+}
+
+pub fn part1(machines: &[Machine]) -> eyre::Result<usize> {
+    machines.iter().try_fold(0usize, |acc, m| {
+        shortest_buttons(m)
+            .map(|c| acc + c)
+            .ok_or_eyre("did not find starting sequence")
+    })
+}
+
+pub fn part2(machines: &[Machine]) -> eyre::Result<usize> {
+    machines.iter().enumerate().try_fold(0usize, |acc, (i, m)| {
+        shortest_joltages(m)
+            .map(|c| acc + c)
+            .ok_or_else(|| eyre!("{i}: did not find joltage sequence"))
+    })
+}
+
+/// `size` machines with 3 to 10 lights and about as many buttons, whose lights and
+/// joltages come from pressing the buttons so that they can always be reached.
+///
+/// Many more buttons than lights makes the joltage search too slow.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut body = String::new();
+    for _ in 0..size {
+        let lights = rng.range(3..=10);
+        let buttons = (0..rng.range(lights - 1..=lights + 2))
+            .map(|_| {
+                let mut button =
+                    (0..lights).filter(|_| rng.chance(0.4)).collect::<Vec<_>>();
+                if button.is_empty() {
+                    button.push(rng.range(0..=lights - 1));
+                }
+                button
+            })
+            .collect::<Vec<_>>();
+        let mut indicators = vec![false; lights];
+        let mut joltages = vec![0u16; lights];
+        for button in &buttons {
+            let toggle = rng.chance(0.5);
+            let presses = rng.range(0..=20);
+            for &light in button {
+                indicators[light] ^= toggle;
+                joltages[light] += presses;
+            }
+        }
+        if !indicators.contains(&true) {
+            for &light in &buttons[0] {
+                indicators[light] = true;
+            }
+        }
+        let indicators =
+            indicators.iter().map(|&on| if on { '#' } else { '.' }).collect::<String>();
+        let buttons =
+            buttons.iter().map(|b| format!("({})", b.iter().join(","))).join(" ");
+        body +=
+            &format!("[{indicators}] {buttons} {{{}}}\n", joltages.iter().join(","));
+    }
+    body
+}
+
+pub struct Factory;
+
+impl Solution for Factory {
+    type Input<'a> = Vec<Machine>;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> eyre::Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(machines: &Self::Input<'_>) -> eyre::Result<Answer> {
+        Ok(part1(machines)?.into())
+    }
+
+    fn part2(machines: &Self::Input<'_>) -> eyre::Result<Answer> {
+        Ok(part2(machines)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part1(machines: &Self::Input<'_>) -> Option<eyre::Result<Answer>> {
+        let presses = machines.iter().map(buttons_by_search).sum::<Option<usize>>();
+        Some(presses.map(Answer::from).ok_or_eyre("did not find starting sequence"))
+    }
+
+    fn reference_part2(machines: &Self::Input<'_>) -> Option<eyre::Result<Answer>> {
+        let mut total = 0;
+        for (i, machine) in machines.iter().enumerate() {
+            match joltages_by_search(machine)? {
+                Some(presses) => total += presses,
+                None => return Some(Err(eyre!("{i}: did not find joltage sequence"))),
+            }
+        }
+        Some(Ok(total.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day10-example.txt");
+
+    #[test]
+    fn example() {
+        let machines = parse("example", EXAMPLE).unwrap();
+        assert_eq!(part1(&machines).unwrap(), 7);
+        assert_eq!(part2(&machines).unwrap(), 33);
+    }
+
+    #[test]
+    fn searches_match_example() {
+        let machines = parse("example", EXAMPLE).unwrap();
+        let buttons = machines.iter().map(buttons_by_search).collect::<Vec<_>>();
+        assert_eq!(buttons, [Some(2), Some(3), Some(2)]);
+        let joltages = machines.iter().map(joltages_by_search).collect::<Vec<_>>();
+        assert_eq!(joltages, [Some(Some(10)), Some(Some(12)), Some(Some(11))]);
+    }
+
+    #[test]
+    fn button_out_of_range() {
+        assert!(parse("range", "[.#] (0,2) {1,1}\n").is_err());
+    }
+
+    #[test]
+    fn joltages_mismatch() {
+        let body = "[.#] (0,1) {1,1}\n[.#] (0,1) {1}\n";
+        let e = parse("mismatch", body).unwrap_err();
+        assert!(e.to_string().starts_with("mismatch:2:1: parsing failed"), "{e}");
+    }
+
+    #[test]
+    fn repeated_indicator() {
+        let e = parse("repeat", "[#] (0) {1}\n[#] (0,0) {1}\n").unwrap_err();
+        assert!(e.to_string().starts_with("repeat:2:1: parsing failed"), "{e}");
+    }
+}
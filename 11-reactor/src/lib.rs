@@ -0,0 +1,297 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use common::{Answer, Rng, Solution};
+use eyre::OptionExt;
+use fxhash::FxBuildHasher;
+use nom::bytes::complete::{tag, take_till1};
+use nom::character::complete::{char, newline};
+use nom::combinator::{all_consuming, opt};
+use nom::multi::separated_list1;
+use nom::sequence::terminated;
+use nom::{IResult, Parser};
+use petgraph::algo::all_simple_paths;
+use petgraph::graphmap::DiGraphMap;
+
+// fn parse_name(delimiter: char) -> impl Fn(&str) -> IResult<&str,&str> {
+//     move |input: &str| {
+//         let mut buffer = [0u8; 4];
+//         let tag: &str = delimiter.encode_utf8(&mut buffer);
+//         //map(take_until1(tag), |s: &str| s.to_string()).parse(input)
+//         take_until1(tag).parse(input)
+//     }
+// }
+
+pub fn parse_name(stops: &str) -> impl Fn(&str) -> IResult<&str, &str> {
+    move |input: &str| take_till1(|c| stops.contains(c)).parse(input)
+}
+
+/// A device name and the devices its outputs connect to.
+pub type Device<'a> = (&'a str, Vec<&'a str>);
+
+pub fn parse_device(input: &str) -> IResult<&str, Device<'_>> {
+    let res = (
+        terminated(parse_name(":"), tag(": ")),
+        separated_list1(char(' '), parse_name(" \n")),
+    )
+        .parse(input)?;
+    Ok(res)
+}
+
+pub fn parse_file(input: &str) -> IResult<&str, Vec<Device<'_>>> {
+    let mut parser =
+        all_consuming(terminated(separated_list1(newline, parse_device), opt(newline)));
+    parser.parse(input)
+}
+
+pub fn make_graph<'a>(devices: &[Device<'a>]) -> DiGraphMap<&'a str, ()> {
+    let num_nodes = devices.len();
+    let mut graph = DiGraphMap::with_capacity(num_nodes, 8 * num_nodes);
+    for (src, dests) in devices {
+        for dest in dests {
+            graph.add_edge(*src, *dest, ());
+        }
+    }
+    graph
+}
+
+pub fn reverse_paths<'a>(
+    devices: &[Device<'a>],
+) -> HashMap<&'a str, Vec<&'a str>, FxBuildHasher> {
+    let mut result: HashMap<&str, Vec<&str>, FxBuildHasher> =
+        HashMap::with_capacity_and_hasher(devices.len(), FxBuildHasher::new());
+    for (input, outputs) in devices {
+        for output in outputs {
+            result.entry(*output).or_default().push(*input);
+        }
+    }
+    result
+}
+
+fn make_limited_graph<'a, S>(
+    devices: &HashMap<&'a str, Vec<&'a str>, S>,
+    from: &'a str,
+    to: &'a str,
+) -> DiGraphMap<&'a str, ()>
+where
+    S: std::hash::BuildHasher,
+{
+    let num_nodes = devices.len();
+    let mut graph = DiGraphMap::with_capacity(num_nodes, 8 * num_nodes);
+    let mut queue = VecDeque::from([to]);
+    while let Some(node) = queue.pop_front() {
+        if node != from
+            && let Some(sources) = devices.get(node)
+        {
+            for &source in sources {
+                if !graph.contains_node(source) {
+                    queue.push_back(source);
+                }
+                graph.add_edge(source, node, ());
+            }
+        }
+    }
+    graph
+}
+
+pub fn count_limited<S>(
+    devices: &HashMap<&str, Vec<&str>, S>,
+    from: &str,
+    to: &str,
+) -> usize
+where
+    S: std::hash::BuildHasher,
+{
+    let graph = make_limited_graph(devices, from, to);
+    if graph.contains_node(from) {
+        all_simple_paths::<Vec<_>, _, FxBuildHasher>(&graph, from, to, 0, None).count()
+    } else {
+        0
+    }
+}
+
+pub fn dsp_paths<S>(devices: &HashMap<&str, Vec<&str>, S>) -> Option<usize>
+where
+    S: std::hash::BuildHasher,
+{
+    let svr_fft_out = {
+        let fft_dac = count_limited(devices, "fft", "dac");
+        if fft_dac > 0 {
+            let svr_fft = count_limited(devices, "svr", "fft");
+            if svr_fft > 0 {
+                let dac_out = count_limited(devices, "dac", "out");
+                dac_out.checked_mul(svr_fft)?.checked_mul(fft_dac)?
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    };
+    let svr_dac_out = {
+        let dac_fft = count_limited(devices, "dac", "fft");
+        if dac_fft > 0 {
+            let svr_dac = count_limited(devices, "svr", "dac");
+            if svr_dac > 0 {
+                let fft_out = count_limited(devices, "fft", "out");
+                dac_fft.checked_mul(svr_dac)?.checked_mul(fft_out)?
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    };
+    svr_fft_out.checked_add(svr_dac_out)
+}
+
+/// Most steps [`paths_one_by_one`] will take.
+pub const MAX_REFERENCE_STEPS: usize = 1_000_000;
+
+/// Counts the paths from `from` to `out` that pass through all of `via` by walking
+/// each of them, or `None` if that takes more than [`MAX_REFERENCE_STEPS`] steps.
+pub fn paths_one_by_one(devices: &[Device], from: &str, via: &[&str]) -> Option<usize> {
+    fn walk<'a>(
+        outputs: &HashMap<&'a str, &'a [&'a str]>,
+        path: &mut Vec<&'a str>,
+        via: &[&str],
+        steps: &mut usize,
+    ) -> Option<usize> {
+        *steps += 1;
+        if *steps > MAX_REFERENCE_STEPS {
+            return None;
+        }
+        let node = *path.last()?;
+        if node == "out" {
+            return Some(usize::from(via.iter().all(|v| path.contains(v))));
+        }
+        let mut count = 0;
+        for &next in outputs.get(node).copied().unwrap_or_default() {
+            if !path.contains(&next) {
+                path.push(next);
+                count += walk(outputs, path, via, steps)?;
+                path.pop();
+            }
+        }
+        Some(count)
+    }
+
+    let outputs = devices.iter().map(|(d, o)| (*d, o.as_slice())).collect();
+    walk(&outputs, &mut vec![from], via, &mut 0)
+}
+
+pub fn parse<'a>(fname: &str, body: &'a str) -> eyre::Result<Vec<Device<'a>>> {
+    common::finish(fname, body, parse_file(body))
+}
+
+pub fn part1(devices: &[Device]) -> usize {
+    let graph = make_graph(devices);
+    all_simple_paths::<Vec<_>, _, fxhash::FxBuildHasher>(&graph, "you", "out", 0, None)
+        .count()
+}
+
+pub fn part2(devices: &[Device]) -> eyre::Result<usize> {
+    dsp_paths(&reverse_paths(devices)).ok_or_eyre("too many paths to count")
+}
+
+/// Layers of devices between the stages of each part's paths.
+const STAGE_LAYERS: usize = 6;
+
+/// Roughly `size` devices in layers, each output going to one or two devices in
+/// the next layer.
+///
+/// `svr`, `fft`, `dac` and `you` sit a few layers apart, in the order the puzzle
+/// expects but with `fft` and `dac` swapped half the time, and every device reaches
+/// `out`. With few layers between them, the paths between stages stay countable one
+/// by one.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let layers = 4 * STAGE_LAYERS;
+    let width = (size / layers).max(1);
+    let mut stages = ["svr", "fft", "dac", "you"];
+    if rng.chance(0.5) {
+        stages.swap(1, 2);
+    }
+    let mut used = HashSet::from(["svr", "fft", "dac", "you", "out"].map(String::from));
+    let mut names = (0..layers)
+        .map(|layer| {
+            (0..width)
+                .map(|i| {
+                    if layer % STAGE_LAYERS == 0 && i == 0 {
+                        return stages[layer / STAGE_LAYERS].to_string();
+                    }
+                    loop {
+                        let name = (0..3)
+                            .map(|_| char::from(rng.range(b'a'..=b'z')))
+                            .collect::<String>();
+                        if used.insert(name.clone()) {
+                            return name;
+                        }
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    names.push(vec!["out".to_string()]);
+    let mut lines = Vec::new();
+    for pair in names.windows(2) {
+        for name in &pair[0] {
+            let mut outputs = pair[1].clone();
+            rng.shuffle(&mut outputs);
+            outputs.truncate(rng.range(1..=2));
+            lines.push(format!("{name}: {}\n", outputs.join(" ")));
+        }
+    }
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+pub struct Reactor;
+
+impl Solution for Reactor {
+    type Input<'a> = Vec<Device<'a>>;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> eyre::Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(devices: &Self::Input<'_>) -> eyre::Result<Answer> {
+        Ok(part1(devices).into())
+    }
+
+    fn part2(devices: &Self::Input<'_>) -> eyre::Result<Answer> {
+        Ok(part2(devices)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part1(devices: &Self::Input<'_>) -> Option<eyre::Result<Answer>> {
+        Some(Ok(paths_one_by_one(devices, "you", &[])?.into()))
+    }
+
+    fn reference_part2(devices: &Self::Input<'_>) -> Option<eyre::Result<Answer>> {
+        Some(Ok(paths_one_by_one(devices, "svr", &["dac", "fft"])?.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day11-example.txt");
+    const EXAMPLE2: &str = include_str!("../../examples/day11-example2.txt");
+
+    #[test]
+    fn example() {
+        let devices = parse("example", EXAMPLE).unwrap();
+        assert_eq!(part1(&devices), 5);
+        assert_eq!(paths_one_by_one(&devices, "you", &[]), Some(5));
+    }
+
+    #[test]
+    fn example2() {
+        let devices = parse("example2", EXAMPLE2).unwrap();
+        assert_eq!(part2(&devices).unwrap(), 2);
+        assert_eq!(paths_one_by_one(&devices, "svr", &["dac", "fft"]), Some(2));
+    }
+}
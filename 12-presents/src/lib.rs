@@ -0,0 +1,385 @@
+use std::collections::HashSet;
+
+use common::{Answer, Grid, Rng, Solution};
+use eyre::eyre;
+use ndarray::{Array2, ArrayRef2, s};
+use nom::bytes::complete::{tag, take_until, take_until1};
+use nom::character::complete::{char, digit1, newline};
+use nom::combinator::{all_consuming, map, map_res, opt};
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{preceded, terminated};
+use nom::{IResult, Parser};
+
+pub fn parse_number<N: std::str::FromStr>(input: &str) -> IResult<&str, N> {
+    map_res(digit1, |s: &str| s.parse::<N>()).parse(input)
+}
+
+pub fn parse_shape(input: &str) -> IResult<&str, Array2<bool>> {
+    let shape = map_res(take_until1("\n\n"), |s: &str| {
+        let shape = Grid::parse_with(s, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(eyre!("unknown shape char: {c}")),
+        })?;
+        eyre::Ok(shape.into_inner())
+    });
+    terminated(shape, tag("\n\n")).parse(input)
+}
+
+pub fn parse_present(input: &str) -> IResult<&str, Array2<bool>> {
+    preceded((take_until(":\n"), tag(":\n")), parse_shape).parse(input)
+}
+
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub width: usize,
+    pub height: usize,
+    pub presents: Vec<usize>,
+}
+
+pub fn parse_region(input: &str) -> IResult<&str, Region> {
+    let parser = (
+        terminated(parse_number, char('x')),
+        terminated(parse_number, tag(": ")),
+        separated_list1(char(' '), parse_number),
+    );
+    map(parser, |(width, height, presents)| Region { width, height, presents })
+        .parse(input)
+}
+
+pub fn parse_file(input: &str) -> IResult<&str, (Vec<Array2<bool>>, Vec<Region>)> {
+    let parser = terminated(
+        (many1(parse_present), separated_list1(newline, parse_region)),
+        opt(newline),
+    );
+    all_consuming(parser).parse(input)
+}
+
+pub fn area(shape: &ArrayRef2<bool>) -> usize {
+    shape.iter().filter(|&&e| e).count()
+}
+
+/// The present shapes and the regions under the trees to fit them into.
+#[derive(Debug, Clone)]
+pub struct Farm {
+    pub presents: Vec<Array2<bool>>,
+    pub regions: Vec<Region>,
+}
+
+pub fn parse(fname: &str, body: &str) -> eyre::Result<Farm> {
+    let (presents, regions) = common::finish(fname, body, parse_file(body))?;
+    for region in regions.iter() {
+        if region.presents.len() != presents.len() {
+            return Err(eyre!("{fname}: region contains wrong number of presents"));
+        }
+    }
+    Ok(Farm { presents, regions })
+}
+
+/// Widens `a * b` so that no region or present count from the input can overflow.
+fn wide_mul(a: usize, b: usize) -> u128 {
+    a as u128 * b as u128
+}
+
+fn fits_by_area(farm: &Farm) -> impl Iterator<Item = &Region> {
+    let areas = farm.presents.iter().map(|p| area(p)).collect::<Vec<_>>();
+    farm.regions.iter().filter(move |r| {
+        let area = wide_mul(r.width, r.height);
+        let needed = r
+            .presents
+            .iter()
+            .zip(areas.iter())
+            .fold(0u128, |sum, (&p, &a)| sum.saturating_add(wide_mul(p, a)));
+        area >= needed
+    })
+}
+
+pub fn part1(farm: &Farm) -> usize {
+    fits_by_area(farm).count()
+}
+
+/// Counts the regions that fit by area but not with every present in its own
+/// bounding box, where only an actual packing could settle the answer.
+pub fn unproven_regions(farm: &Farm) -> usize {
+    let rows = farm.presents.iter().map(|p| p.nrows()).max().unwrap_or(1);
+    let cols = farm.presents.iter().map(|p| p.ncols()).max().unwrap_or(1);
+    fits_by_area(farm)
+        .filter(|r| {
+            let boxes = wide_mul(r.width / cols, r.height / rows);
+            boxes
+                < r.presents.iter().fold(0u128, |sum, &p| sum.saturating_add(p as u128))
+        })
+        .count()
+}
+
+/// Most steps [`packable_regions`] will take over all regions.
+pub const MAX_REFERENCE_STEPS: usize = 1_000_000;
+
+/// Every distinct rotation and reflection of `shape`, each as the offsets of its
+/// cells from its first cell in reading order.
+fn orientations(shape: &Array2<bool>) -> Vec<Vec<(isize, isize)>> {
+    let mut result = Vec::new();
+    let mut shape = shape.clone();
+    for _ in 0..2 {
+        for _ in 0..4 {
+            let cells = shape
+                .indexed_iter()
+                .filter(|&(_, &cell)| cell)
+                .map(|((y, x), _)| (y as isize, x as isize))
+                .collect::<Vec<_>>();
+            if let Some(&(y0, x0)) = cells.first() {
+                let offsets = cells.iter().map(|(y, x)| (y - y0, x - x0)).collect();
+                if !result.contains(&offsets) {
+                    result.push(offsets);
+                }
+            }
+            shape = shape.t().slice(s![.., ..;-1]).to_owned();
+        }
+        shape = shape.slice(s![.., ..;-1]).to_owned();
+    }
+    result
+}
+
+/// For each cell of `region`, the cells `shape` covers in every orientation that
+/// puts its first cell there and stays inside, one bit per cell in reading order.
+fn placements(region: &Region, shape: &[Vec<(isize, isize)>]) -> Vec<Vec<u128>> {
+    let (width, height) = (region.width as isize, region.height as isize);
+    let mask = |y: isize, x: isize, offsets: &[(isize, isize)]| {
+        offsets.iter().try_fold(0u128, |mask, &(dy, dx)| {
+            let (y, x) = (y + dy, x + dx);
+            let inside = (0..height).contains(&y) && (0..width).contains(&x);
+            inside.then(|| mask | 1 << (y * width + x))
+        })
+    };
+    (0..width * height)
+        .map(|cell| {
+            let (y, x) = (cell / width, cell % width);
+            shape.iter().filter_map(|offsets| mask(y, x, offsets)).collect()
+        })
+        .collect()
+}
+
+/// State of [`packable_regions`] while packing one region of at most 128 cells.
+struct Packing {
+    /// [`placements`] of each present.
+    placements: Vec<Vec<Vec<u128>>>,
+    /// Bits of the cells in the region.
+    region: u128,
+    left: Vec<usize>,
+    /// Cells that may still be left empty.
+    slack: usize,
+    steps: usize,
+    /// Fillings and presents left over that are known not to pack.
+    failed: HashSet<(u128, Vec<usize>)>,
+}
+
+impl Packing {
+    /// Counts the free cells that no present still left could cover; they all have
+    /// to be left empty.
+    fn dead_cells(&self, filled: u128) -> usize {
+        let mut coverable = 0u128;
+        let still_left =
+            self.placements.iter().zip(&self.left).filter(|(_, n)| **n > 0);
+        for (by_cell, _) in still_left {
+            for &mask in by_cell.iter().flatten() {
+                if mask & filled == 0 {
+                    coverable |= mask;
+                }
+            }
+        }
+        (self.region & !filled & !coverable).count_ones() as usize
+    }
+
+    /// Whether the presents still left fit into the cells not in `filled`.
+    ///
+    /// The first free cell is either covered by the first cell of a present or left
+    /// empty. Returns `None` once the steps run out.
+    fn pack(&mut self, filled: u128) -> Option<bool> {
+        self.steps = self.steps.checked_sub(1)?;
+        if self.left.iter().all(|&n| n == 0) {
+            return Some(true);
+        }
+        let free = self.region & !filled;
+        if free == 0
+            || self.dead_cells(filled) > self.slack
+            || self.failed.contains(&(filled, self.left.clone()))
+        {
+            return Some(false);
+        }
+        let cell = free.trailing_zeros() as usize;
+        for i in 0..self.left.len() {
+            for j in 0..self.placements[i][cell].len() {
+                let mask = self.placements[i][cell][j];
+                if self.left[i] == 0 || mask & filled != 0 {
+                    continue;
+                }
+                self.left[i] -= 1;
+                let packed = self.pack(filled | mask);
+                self.left[i] += 1;
+                if packed? {
+                    return Some(true);
+                }
+            }
+        }
+        let mut packed = Some(false);
+        if self.slack > 0 {
+            self.slack -= 1;
+            packed = self.pack(filled | 1 << cell);
+            self.slack += 1;
+        }
+        if packed == Some(false) {
+            self.failed.insert((filled, self.left.clone()));
+        }
+        packed
+    }
+}
+
+/// Part 1 by actually packing the presents into each region, trying every
+/// placement of every present.
+///
+/// Returns `None` if a region that fits by area has more than 128 cells, or packing
+/// takes more than [`MAX_REFERENCE_STEPS`] steps.
+pub fn packable_regions(farm: &Farm) -> Option<usize> {
+    let shapes = farm.presents.iter().map(orientations).collect::<Vec<_>>();
+    let mut steps = MAX_REFERENCE_STEPS;
+    let mut count = 0;
+    for region in &farm.regions {
+        let cells = region.width.checked_mul(region.height)?;
+        let mut needed = 0usize;
+        for (&n, present) in region.presents.iter().zip(&farm.presents) {
+            needed = needed.checked_add(n.checked_mul(area(present))?)?;
+        }
+        if needed > cells {
+            continue;
+        }
+        if cells > 128 {
+            return None;
+        }
+        // Presents without any cells take no room.
+        let left = region.presents.iter().zip(&shapes);
+        let left = left.map(|(&n, shape)| if shape.is_empty() { 0 } else { n });
+        let mut packing = Packing {
+            placements: shapes.iter().map(|shape| placements(region, shape)).collect(),
+            region: u128::MAX.checked_shr(128 - cells as u32).unwrap_or(0),
+            left: left.collect(),
+            slack: cells - needed,
+            steps,
+            failed: HashSet::new(),
+        };
+        if packing.pack(0)? {
+            count += 1;
+        }
+        steps = packing.steps;
+    }
+    Some(count)
+}
+
+/// Six 3x3 presents and `size` regions of 35 to 50 units a side, holding about as
+/// many presents as fit by area.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut body = String::new();
+    let mut areas = Vec::new();
+    for i in 0..6 {
+        let mut shape = Array2::from_shape_simple_fn((3, 3), || rng.chance(0.7));
+        shape[(1, 1)] = true;
+        areas.push(area(&shape));
+        let shape = Grid::from(shape).render(|&cell| if cell { '#' } else { '.' });
+        body += &format!("{i}:\n{shape}\n");
+    }
+    for _ in 0..size {
+        let (width, height) = (rng.range(35..=50), rng.range(35..=50));
+        let fill =
+            (width * height) as f64 * (0.6 + 0.5 * rng.range(0..=100) as f64 / 100.0);
+        let mut counts = vec![0usize; areas.len()];
+        let mut used = 0;
+        while (used as f64) < fill {
+            let i = rng.range(0..=areas.len() - 1);
+            counts[i] += 1;
+            used += areas[i];
+        }
+        let counts = counts.iter().map(usize::to_string).collect::<Vec<_>>();
+        body += &format!("{width}x{height}: {}\n", counts.join(" "));
+    }
+    body
+}
+
+pub struct Presents;
+
+impl Solution for Presents {
+    type Input<'a> = Farm;
+
+    const HAS_PART2: bool = false;
+
+    fn parse<'a>(fname: &str, body: &'a str) -> eyre::Result<Self::Input<'a>> {
+        parse(fname, body)
+    }
+
+    fn part1(farm: &Self::Input<'_>) -> eyre::Result<Answer> {
+        Ok(part1(farm).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn reference_part1(farm: &Self::Input<'_>) -> Option<eyre::Result<Answer>> {
+        Some(Ok(packable_regions(farm)?.into()))
+    }
+
+    fn warnings(farm: &Self::Input<'_>) -> Vec<String> {
+        match unproven_regions(farm) {
+            0 => Vec::new(),
+            n => vec![format!(
+                "{n} regions were accepted on area alone without checking the packing"
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../examples/day12-example.txt");
+
+    #[test]
+    fn example() {
+        let farm = parse("example", EXAMPLE).unwrap();
+        assert_eq!(farm.presents.len(), 6);
+        assert_eq!(farm.regions.len(), 3);
+        // The published answer is 2: the last region has the area for its presents
+        // but they cannot be packed. Real inputs are never that tight.
+        assert_eq!(part1(&farm), 3);
+        assert_eq!(unproven_regions(&farm), 3);
+        assert_eq!(packable_regions(&farm), Some(2));
+    }
+
+    #[test]
+    fn wrong_number_of_presents() {
+        assert!(parse("count", "0:\n##\n#.\n\n2x2: 1 1\n").is_err());
+    }
+
+    #[test]
+    fn single_row_shape() {
+        let farm = parse("row", "0:\n###\n\n3x1: 1\n").unwrap();
+        assert_eq!(farm.presents[0].dim(), (1, 3));
+        assert_eq!(part1(&farm), 1);
+    }
+
+    #[test]
+    fn huge_region() {
+        let max = usize::MAX;
+        let farm = parse("huge", &format!("0:\n#\n\n{max}x{max}: {max}\n")).unwrap();
+        assert_eq!(part1(&farm), 1);
+        let farm = parse("huge", &format!("0:\n##\n\n2x2: {max}\n")).unwrap();
+        assert_eq!(part1(&farm), 0);
+    }
+
+    #[test]
+    fn empty_region() {
+        let body = "0:\n#\n\n0x3: 0\n";
+        let farm = parse("empty", body).unwrap();
+        assert_eq!(packable_regions(&farm), Some(part1(&farm)));
+        assert_eq!(common::compare_reference::<Presents>("empty", body).unwrap(), 1);
+    }
+}
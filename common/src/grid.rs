@@ -0,0 +1,165 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use eyre::{Result, eyre};
+use ndarray::Array2;
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const NEIGHBOURS8: [(isize, isize); 8] =
+    [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// A rectangular grid read from text, one row per line and one cell per character.
+///
+/// Derefs to the underlying [`Array2`], which is indexed by `(row, column)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses `body`, decoding each character with `decode`.
+    ///
+    /// Errors start with the 1-based line, and column for cells that fail to decode,
+    /// so callers only need to prefix the file name.
+    pub fn parse_with<E: fmt::Display>(
+        body: &str,
+        mut decode: impl FnMut(char) -> std::result::Result<T, E>,
+    ) -> Result<Self> {
+        let mut rows = 0usize;
+        let mut columns = 0usize;
+        let mut cells = Vec::new();
+        for line in body.lines() {
+            let width = line.chars().count();
+            if rows == 0 {
+                columns = width;
+            } else if width != columns {
+                return Err(eyre!(
+                    "{}: expected {columns} cells like the first line, found {width}",
+                    rows + 1
+                ));
+            }
+            for (i, c) in line.chars().enumerate() {
+                cells
+                    .push(decode(c).map_err(|e| eyre!("{}:{}: {e}", rows + 1, i + 1))?);
+            }
+            rows += 1;
+        }
+        Ok(Self { cells: Array2::from_shape_vec((rows, columns), cells)? })
+    }
+
+    /// Parses `body` using the cell type's own decoding, see [`Grid::parse_with`].
+    pub fn parse(body: &str) -> Result<Self>
+    where
+        T: TryFrom<char>,
+        T::Error: fmt::Display,
+    {
+        Self::parse_with(body, T::try_from)
+    }
+
+    pub fn into_inner(self) -> Array2<T> {
+        self.cells
+    }
+
+    /// Positions of the cells above, left of, right of and below `pos` that lie
+    /// within the grid.
+    pub fn neighbours4(
+        &self,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(pos, &NEIGHBOURS4)
+    }
+
+    /// Like [`Grid::neighbours4`], but also including the diagonals.
+    pub fn neighbours8(
+        &self,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(pos, &NEIGHBOURS8)
+    }
+
+    fn offsets(
+        &self,
+        (y, x): (usize, usize),
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (rows, cols) = self.cells.dim();
+        offsets.iter().filter_map(move |&(dy, dx)| {
+            let pos = (y.checked_add_signed(dy)?, x.checked_add_signed(dx)?);
+            (pos.0 < rows && pos.1 < cols).then_some(pos)
+        })
+    }
+
+    /// Writes the grid back out as text, encoding each cell with `encode`.
+    pub fn render(&self, mut encode: impl FnMut(&T) -> char) -> String {
+        let mut text = String::with_capacity(self.cells.len() + self.cells.nrows());
+        for row in self.cells.rows() {
+            text.extend(row.iter().map(&mut encode));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T> From<Array2<T>> for Grid<T> {
+    fn from(cells: Array2<T>) -> Self {
+        Self { cells }
+    }
+}
+
+impl<T> Deref for Grid<T> {
+    type Target = Array2<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cells
+    }
+}
+
+impl<T> DerefMut for Grid<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cells
+    }
+}
+
+impl<T: Copy + Into<char>> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(|&cell| cell.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_render() {
+        let grid = Grid::<char>::parse("ab\ncd\nef\n").unwrap();
+        assert_eq!(grid.dim(), (3, 2));
+        assert_eq!(grid[(2, 0)], 'e');
+        assert_eq!(grid.to_string(), "ab\ncd\nef\n");
+        assert_eq!(grid.render(|c| c.to_ascii_uppercase()), "AB\nCD\nEF\n");
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let err = Grid::<char>::parse("abc\nab\n").unwrap_err();
+        assert_eq!(err.to_string(), "2: expected 3 cells like the first line, found 2");
+        let err = Grid::parse_with("..\n.x\n", |c| match c {
+            '.' => Ok(()),
+            _ => Err(format!("unknown cell {c:?}")),
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "2:2: unknown cell 'x'");
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = Grid::<char>::parse("abc\ndef\n").unwrap();
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), [(0, 1), (1, 0)]);
+        assert_eq!(
+            grid.neighbours8((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+    }
+}
@@ -0,0 +1,263 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use eyre::{Result, WrapErr};
+use nom::IResult;
+
+mod grid;
+mod rng;
+mod solution;
+mod timing;
+
+pub use grid::Grid;
+pub use rng::Rng;
+pub use solution::{Solution, compare_reference, solve};
+pub use timing::{Phase, Stats, Timer};
+
+/// A single puzzle answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(n) => write!(f, "{n}"),
+            Self::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Self::Int(i128::from(value))
+            }
+        })*
+    };
+}
+
+answer_from_int!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Int(i128::try_from(value).expect("usize fits in i128"))
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Self::Int(i128::try_from(value).expect("isize fits in i128"))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+/// The answers of one day; some days only have a first part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Answer,
+    pub part2: Option<Answer>,
+    /// Caveats about the answers that do not make them wrong, such as shortcuts
+    /// taken by the solver.
+    pub warnings: Vec<String>,
+}
+
+impl Answers {
+    pub fn new(part1: impl Into<Answer>, part2: impl Into<Answer>) -> Self {
+        Self { part1: part1.into(), part2: Some(part2.into()), warnings: Vec::new() }
+    }
+
+    pub fn part1_only(part1: impl Into<Answer>) -> Self {
+        Self { part1: part1.into(), part2: None, warnings: Vec::new() }
+    }
+
+    pub fn with_warning(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+}
+
+/// Body of each day's `main`: solves each file named on the command line, or
+/// standard input if there are none, and prints one answer per line.
+///
+/// With several inputs, each file's answers follow a line naming it.
+pub fn run_day<S: Solution>() -> Result<()> {
+    solve_files::<S>(&std::env::args().skip(1).collect::<Vec<_>>())
+}
+
+/// [`run_day`] for the files in `fnames`, for days whose `main` takes options too.
+pub fn solve_files<S: Solution>(fnames: &[String]) -> Result<()> {
+    let stdin = [STDIN.to_string()];
+    let fnames = if fnames.is_empty() { &stdin[..] } else { fnames };
+    for fname in fnames {
+        let body = read_input(fname)?;
+        let name = display_name(fname);
+        let answers = solve::<S>(name, &body, &mut Timer::default())?;
+        if fnames.len() > 1 {
+            println!("{name}:");
+        }
+        println!("{}", answers.part1);
+        if let Some(part2) = answers.part2 {
+            println!("{part2}");
+        }
+        for warning in answers.warnings {
+            eprintln!("{name}: warning: {warning}");
+        }
+    }
+    Ok(())
+}
+
+/// File name that stands for standard input.
+pub const STDIN: &str = "-";
+
+/// Name to use for `fname` in messages.
+pub fn display_name(fname: &str) -> &str {
+    if fname == STDIN { "<stdin>" } else { fname }
+}
+
+/// Reads `fname`, or all of standard input if it is [`STDIN`].
+pub fn read_input(fname: &str) -> Result<String> {
+    if fname == STDIN {
+        std::io::read_to_string(std::io::stdin()).wrap_err("<stdin>: read failed")
+    } else {
+        std::fs::read_to_string(fname).wrap_err_with(|| format!("{fname}: read failed"))
+    }
+}
+
+/// Puts `body` into the shape every day's parser expects: no byte order mark,
+/// `\n` line endings, no trailing whitespace on any line and exactly one newline
+/// after the last non-blank line.
+///
+/// Interior blank lines are kept, so line numbers in parse errors still match the
+/// original file.
+pub fn normalize(body: &str) -> Cow<'_, str> {
+    let body = body.strip_prefix('\u{feff}').unwrap_or(body);
+    let content = body.trim_end_matches(char::is_whitespace);
+    let final_newline = if content.is_empty() { "" } else { "\n" };
+    if body.strip_suffix(final_newline) == Some(content)
+        && !content.contains('\r')
+        && content.lines().all(|line| line.trim_end() == line)
+    {
+        return Cow::Borrowed(body);
+    }
+    let mut normalized = String::with_capacity(body.len());
+    for line in content.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    Cow::Owned(normalized)
+}
+
+/// A nom failure located within the input it was parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: String,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, counted in characters.
+    pub column: usize,
+    pub source_line: String,
+    pub kind: nom::error::ErrorKind,
+}
+
+impl ParseError {
+    /// Locates `err` within `body`.
+    ///
+    /// The error's remaining input must be a subslice of `body`, which is always true
+    /// when the parser was handed `body` or a slice of it such as a single line.
+    pub fn new(fname: &str, body: &str, err: nom::error::Error<&str>) -> Self {
+        let offset = subslice_offset(body, err.input);
+        let line_start = body[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = body[offset..].find('\n').map_or(body.len(), |i| offset + i);
+        let source_line = body[line_start..line_end].trim_end_matches('\r');
+        Self {
+            file: fname.to_string(),
+            line: body[..line_start].matches('\n').count() + 1,
+            column: body[line_start..offset].chars().count() + 1,
+            source_line: source_line.to_string(),
+            kind: err.code,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}:{}:{}: parsing failed: {}",
+            self.file,
+            self.line,
+            self.column,
+            self.kind.description()
+        )?;
+        writeln!(f, "{}", self.source_line)?;
+        // Keep tabs so the caret lines up with the source line in a terminal.
+        let padding = self
+            .source_line
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        write!(f, "{padding}^")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Byte offset of `part` within `whole`, clamped to `whole` if it is not a subslice.
+fn subslice_offset(whole: &str, part: &str) -> usize {
+    let start = whole.as_ptr() as usize;
+    let offset = (part.as_ptr() as usize).wrapping_sub(start);
+    if offset <= whole.len() && whole.is_char_boundary(offset) {
+        return offset;
+    }
+    let offset = whole.len().saturating_sub(part.len());
+    if whole.is_char_boundary(offset) { offset } else { whole.len() }
+}
+
+/// Like nom's `Finish`, but turns a failure into a [`ParseError`] pointing into `body`.
+pub fn finish<'a, O>(
+    fname: &str,
+    body: &'a str,
+    result: IResult<&'a str, O>,
+) -> Result<O> {
+    match result {
+        Ok((_, v)) => Ok(v),
+        Err(nom::Err::Incomplete(_)) => unreachable!(),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            Err(ParseError::new(fname, body, e).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_clean_input_is_borrowed() {
+        assert!(matches!(normalize("a\n\nb\n"), Cow::Borrowed("a\n\nb\n")));
+        assert!(matches!(normalize(""), Cow::Borrowed("")));
+    }
+
+    #[test]
+    fn normalize_line_endings_and_bom() {
+        assert_eq!(normalize("\u{feff}a\r\nb\r\n"), "a\nb\n");
+        assert_eq!(normalize("a\r\n\r\nb"), "a\n\nb\n");
+    }
+
+    #[test]
+    fn normalize_trailing_whitespace() {
+        assert_eq!(normalize("a  \n\tb\t\n"), "a\n\tb\n");
+        assert_eq!(normalize("a\nb\n\n\n  \n"), "a\nb\n");
+        assert_eq!(normalize(" \r\n\n"), "");
+    }
+}
@@ -0,0 +1,93 @@
+use std::ops::RangeInclusive;
+
+/// Small deterministic random number generator (SplitMix64) for generating inputs.
+///
+/// The same seed always gives the same sequence, on every platform and version, so a
+/// failing generated input can be reproduced from its seed alone.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range<T>(&mut self, range: RangeInclusive<T>) -> T
+    where
+        T: Copy + TryInto<i128> + TryFrom<i128>,
+    {
+        let (Ok(start), Ok(end)) =
+            ((*range.start()).try_into(), (*range.end()).try_into())
+        else {
+            unreachable!("integers up to 64 bits fit in i128");
+        };
+        assert!(start <= end, "empty range");
+        let span = (end - start).unsigned_abs() + 1;
+        let offset = u128::from(self.next_u64()) % span;
+        match T::try_from(start + offset as i128) {
+            Ok(value) => value,
+            Err(_) => unreachable!("value lies within the range"),
+        }
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    /// A random element of `items`, which must not be empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn range_is_inclusive() {
+        let mut rng = Rng::new(0);
+        let values = (0..1000).map(|_| rng.range(-2isize..=2)).collect::<Vec<_>>();
+        assert!(values.iter().all(|v| (-2..=2).contains(v)));
+        assert!(values.contains(&-2) && values.contains(&2));
+        assert_eq!(rng.range(5u8..=5), 5);
+        rng.range(0..=u64::MAX);
+    }
+
+    #[test]
+    fn shuffle_keeps_elements() {
+        let mut items = (0..20).collect::<Vec<_>>();
+        Rng::new(3).shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,181 @@
+use eyre::{Result, eyre};
+
+use crate::{Answer, Answers, Rng, Timer};
+
+/// One day's puzzle: how to parse its input and solve each part.
+///
+/// Days implement this on a unit struct, so runners can be written once for all of
+/// them, e.g. `common::solve::<combo::Combo>`.
+pub trait Solution {
+    /// Parsed input, which may borrow from the text it was parsed from.
+    type Input<'a>;
+
+    /// Whether the puzzle has a second part; [`Solution::part2`] is only called if so.
+    const HAS_PART2: bool = true;
+
+    /// Parses `body`, which has already been through [`crate::normalize`].
+    ///
+    /// `fname` is only used in error messages.
+    fn parse<'a>(fname: &str, body: &'a str) -> Result<Self::Input<'a>>;
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer>;
+
+    fn part2(_input: &Self::Input<'_>) -> Result<Answer> {
+        Err(eyre!("there is no part 2"))
+    }
+
+    /// A random, valid puzzle input of roughly `size` lines or items.
+    ///
+    /// What `size` counts depends on the day; generators keep whatever else the
+    /// solvers rely on, such as numbers not overflowing, close to the real inputs.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+
+    /// Caveats about the answers for `input`, see [`Answers::warnings`].
+    fn warnings(_input: &Self::Input<'_>) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// A slow but obviously correct version of [`Solution::part1`] to check it
+    /// against, or `None` if `input` is too large for it.
+    fn reference_part1(_input: &Self::Input<'_>) -> Option<Result<Answer>> {
+        None
+    }
+
+    /// Like [`Solution::reference_part1`], for [`Solution::part2`].
+    fn reference_part2(_input: &Self::Input<'_>) -> Option<Result<Answer>> {
+        None
+    }
+}
+
+/// Normalizes and parses `body`, then solves both parts, timing each phase.
+pub fn solve<S: Solution>(
+    fname: &str,
+    body: &str,
+    timer: &mut Timer,
+) -> Result<Answers> {
+    let body = &crate::normalize(body);
+    let input = timer.parse(|| S::parse(fname, body))?;
+    let part1 = timer.part1(|| S::part1(&input))?;
+    let mut answers = if S::HAS_PART2 {
+        Answers::new(part1, timer.part2(|| S::part2(&input))?)
+    } else {
+        Answers::part1_only(part1)
+    };
+    answers.warnings = S::warnings(&input);
+    Ok(answers)
+}
+
+/// Solves `body` with both the solver and its reference implementation, failing
+/// at the first part where they disagree.
+///
+/// Returns how many parts were compared; parts whose reference declines the input
+/// are skipped. The two only agree on an error if both fail.
+pub fn compare_reference<S: Solution>(fname: &str, body: &str) -> Result<usize> {
+    let body = &crate::normalize(body);
+    let input = S::parse(fname, body)?;
+    let mut parts = vec![(1, S::part1(&input), S::reference_part1(&input))];
+    if S::HAS_PART2 {
+        parts.push((2, S::part2(&input), S::reference_part2(&input)));
+    }
+    let mut compared = 0;
+    for (part, answer, reference) in parts {
+        let Some(reference) = reference else { continue };
+        let wrong = match (answer, reference) {
+            (Ok(a), Ok(r)) if a == r => None,
+            (Err(_), Err(_)) => None,
+            (Ok(a), Ok(r)) => Some(format!("is {a} but the reference says {r}")),
+            (Ok(a), Err(e)) => Some(format!("is {a} but the reference failed: {e}")),
+            (Err(e), Ok(r)) => Some(format!("failed but the reference says {r}: {e}")),
+        };
+        if let Some(wrong) = wrong {
+            return Err(eyre!("{fname}: part {part} {wrong}"));
+        }
+        compared += 1;
+    }
+    Ok(compared)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Phase;
+
+    /// Sums the numbers on each line, with a warning for each line that is empty.
+    struct Sums;
+
+    impl Solution for Sums {
+        type Input<'a> = Vec<&'a str>;
+
+        const HAS_PART2: bool = false;
+
+        fn parse<'a>(_fname: &str, body: &'a str) -> Result<Self::Input<'a>> {
+            Ok(body.lines().collect())
+        }
+
+        fn part1(lines: &Self::Input<'_>) -> Result<Answer> {
+            let mut sum = 0i64;
+            for n in lines.iter().flat_map(|line| line.split_whitespace()) {
+                sum += n.parse::<i64>()?;
+            }
+            Ok(sum.into())
+        }
+
+        fn generate(rng: &mut Rng, size: usize) -> String {
+            (0..size).map(|_| format!("{}\n", rng.range(0..=9))).collect()
+        }
+
+        fn warnings(lines: &Self::Input<'_>) -> Vec<String> {
+            let empty = lines.iter().filter(|line| line.is_empty()).count();
+            (0..empty).map(|_| "empty line".to_string()).collect()
+        }
+
+        /// Only looks at the first number on each line, so it disagrees with part 1
+        /// on lines with several; declines more than three lines.
+        fn reference_part1(lines: &Self::Input<'_>) -> Option<Result<Answer>> {
+            if lines.len() > 3 {
+                return None;
+            }
+            let first = lines.iter().filter_map(|line| line.split_whitespace().next());
+            Some(
+                first
+                    .map(|n| Ok(n.parse::<i64>()?))
+                    .sum::<Result<i64>>()
+                    .map(Answer::from),
+            )
+        }
+    }
+
+    #[test]
+    fn solve_normalizes_and_times() {
+        let mut timer = Timer::default();
+        let answers =
+            solve::<Sums>("sums", "\u{feff}1 2\r\n\r\n3\r\n", &mut timer).unwrap();
+        assert_eq!(answers.part1, Answer::Int(6));
+        assert_eq!(answers.part2, None);
+        assert_eq!(answers.warnings, ["empty line"]);
+        assert_eq!(timer.samples(Phase::Part1).len(), 1);
+        assert!(timer.samples(Phase::Part2).is_empty());
+    }
+
+    #[test]
+    fn solve_reports_part_errors() {
+        assert!(solve::<Sums>("sums", "1 x\n", &mut Timer::default()).is_err());
+    }
+
+    #[test]
+    fn reference_agrees() {
+        assert_eq!(compare_reference::<Sums>("sums", "1\r\n\r\n2\r\n").unwrap(), 1);
+        assert_eq!(compare_reference::<Sums>("sums", "x\n").unwrap(), 1);
+        assert_eq!(compare_reference::<Sums>("sums", "1\n2\n3\n4 5\n").unwrap(), 0);
+    }
+
+    #[test]
+    fn reference_disagrees() {
+        let err = compare_reference::<Sums>("sums", "1 2\n").unwrap_err();
+        assert_eq!(err.to_string(), "sums: part 1 is 3 but the reference says 1");
+        let err = compare_reference::<Sums>("sums", "1 x\n").unwrap_err();
+        assert!(
+            err.to_string().starts_with("sums: part 1 failed but the reference says 1")
+        );
+    }
+}
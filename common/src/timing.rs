@@ -0,0 +1,110 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Read,
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 4] = [Phase::Read, Phase::Parse, Phase::Part1, Phase::Part2];
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Read => "read",
+            Self::Parse => "parse",
+            Self::Part1 => "part 1",
+            Self::Part2 => "part 2",
+        };
+        f.pad(name)
+    }
+}
+
+/// Wall-clock samples for each phase of solving a day.
+///
+/// Every call to [`Timer::time`] adds one sample, so running a day repeatedly with
+/// the same timer collects the samples needed for [`Timer::stats`].
+#[derive(Debug, Clone, Default)]
+pub struct Timer {
+    samples: [Vec<Duration>; 4],
+}
+
+impl Timer {
+    pub fn time<T>(&mut self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.samples[phase as usize].push(start.elapsed());
+        result
+    }
+
+    pub fn parse<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        self.time(Phase::Parse, f)
+    }
+
+    pub fn part1<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        self.time(Phase::Part1, f)
+    }
+
+    pub fn part2<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        self.time(Phase::Part2, f)
+    }
+
+    pub fn samples(&self, phase: Phase) -> &[Duration] {
+        &self.samples[phase as usize]
+    }
+
+    /// Total time spent in `phase`, or `None` if it never ran.
+    pub fn total(&self, phase: Phase) -> Option<Duration> {
+        let samples = self.samples(phase);
+        (!samples.is_empty()).then(|| samples.iter().sum())
+    }
+
+    pub fn stats(&self, phase: Phase) -> Option<Stats> {
+        let mut samples = self.samples(phase).to_vec();
+        samples.sort_unstable();
+        Some(Stats {
+            min: *samples.first()?,
+            median: samples[samples.len() / 2],
+            max: *samples.last()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_samples() {
+        let mut timer = Timer::default();
+        for ms in [5, 1, 3] {
+            timer.samples[Phase::Part1 as usize].push(Duration::from_millis(ms));
+        }
+        let stats = timer.stats(Phase::Part1).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+        assert_eq!(timer.total(Phase::Part1), Some(Duration::from_millis(9)));
+    }
+
+    #[test]
+    fn phase_never_run() {
+        let mut timer = Timer::default();
+        assert_eq!(timer.parse(|| 7), 7);
+        assert_eq!(timer.samples(Phase::Parse).len(), 1);
+        assert_eq!(timer.stats(Phase::Part2), None);
+        assert_eq!(timer.total(Phase::Part2), None);
+    }
+}
@@ -0,0 +1,21 @@
+//! Fuzz targets for every day, run with cargo-fuzz on a nightly toolchain:
+//!
+//! ```sh
+//! cargo +nightly fuzz run day01 -- -timeout=5
+//! ```
+//!
+//! Each target parses the fuzzer's input and, if that succeeds, solves both parts.
+//! Any panic is a bug: bad input must come back as an error. Some solvers take
+//! exponential time or loop over every number in a range, so inputs that only time
+//! out are expected and not worth fixing.
+//!
+//! `corpus/dayNN` starts out with the example inputs.
+
+use common::{Solution, Timer};
+
+/// Solves `data` as day `S`'s input if it is valid UTF-8, ignoring any error.
+pub fn solve<S: Solution>(data: &[u8]) {
+    if let Ok(body) = std::str::from_utf8(data) {
+        let _ = common::solve::<S>("fuzz", body, &mut Timer::default());
+    }
+}
@@ -0,0 +1,176 @@
+use std::path::{Path, PathBuf};
+
+use eyre::{Result, WrapErr, eyre};
+
+use crate::days::{Day, Selection};
+
+/// Environment variable naming the directory of puzzle inputs, for when
+/// `--inputs-dir` is not given.
+pub const INPUTS_VAR: &str = "AOC_INPUTS";
+
+/// Where `--examples` looks for `dayNN-*.txt` files.
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// One input to solve a day with, and a label for its output.
+#[derive(Debug)]
+pub struct Job {
+    pub day: &'static Day,
+    pub label: String,
+    /// The file to read, or why there is none.
+    pub path: Result<PathBuf>,
+}
+
+impl Job {
+    fn new(day: &'static Day, named: bool, path: Result<PathBuf>) -> Self {
+        let label = match &path {
+            Ok(path) if named => format!("{day} {}", path.display()),
+            _ => day.to_string(),
+        };
+        Self { day, label, path }
+    }
+}
+
+/// The inputs to solve the selected days with: each of `inputs` if there are any,
+/// with `all` taking directories holding `dayNN.txt` files; otherwise every
+/// example in `examples` if it is given; otherwise each day's `dayNN.txt` in
+/// `dir`.
+pub fn find(
+    day: Selection,
+    inputs: &[PathBuf],
+    dir: &Path,
+    examples: Option<&Path>,
+) -> Result<Vec<Job>> {
+    if let Some(examples) = examples {
+        let mut jobs = Vec::new();
+        for d in day.days() {
+            match find_examples(examples, d) {
+                Ok(paths) => {
+                    jobs.extend(paths.into_iter().map(|p| Job::new(d, true, Ok(p))))
+                }
+                Err(e) => jobs.push(Job::new(d, false, Err(e))),
+            }
+        }
+        return Ok(jobs);
+    }
+    if inputs.is_empty() {
+        return Ok(day
+            .days()
+            .iter()
+            .map(|d| Job::new(d, false, puzzle_input(dir, d)))
+            .collect());
+    }
+    if day == Selection::All && inputs.iter().any(|i| i.as_os_str() == common::STDIN) {
+        return Err(eyre!("all: needs input directories rather than standard input"));
+    }
+    let named = inputs.len() > 1;
+    Ok(inputs
+        .iter()
+        .flat_map(|input| {
+            day.days().iter().map(move |d| {
+                let path = match day {
+                    Selection::All => input.join(file_name(d)),
+                    Selection::One(_) => input.clone(),
+                };
+                Job::new(d, named, Ok(path))
+            })
+        })
+        .collect())
+}
+
+fn file_name(day: &Day) -> String {
+    format!("day{:02}.txt", day.number)
+}
+
+/// `dayNN.txt` in `dir`, or an error naming it if it does not exist.
+fn puzzle_input(dir: &Path, day: &Day) -> Result<PathBuf> {
+    let path = dir.join(file_name(day));
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err(eyre!(
+            "{} not found; save the puzzle input there, or point --inputs-dir or \
+             {INPUTS_VAR} at the directory holding it",
+            path.display()
+        ))
+    }
+}
+
+/// Every `dayNN-*.txt` file in `dir`, in name order.
+fn find_examples(dir: &Path, day: &Day) -> Result<Vec<PathBuf>> {
+    let prefix = format!("day{:02}-", day.number);
+    let entries = std::fs::read_dir(dir)
+        .wrap_err_with(|| format!("{}: cannot list examples", dir.display()))?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let name = entry?.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with(&prefix) && name.ends_with(".txt") {
+            paths.push(dir.join(&*name));
+        }
+    }
+    if paths.is_empty() {
+        return Err(eyre!("{}: no {prefix}*.txt examples", dir.display()));
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn examples_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(EXAMPLES_DIR)
+    }
+
+    fn paths(jobs: &[Job]) -> Vec<String> {
+        jobs.iter()
+            .map(|job| match &job.path {
+                Ok(path) => path.file_name().unwrap().to_string_lossy().into_owned(),
+                Err(e) => format!("error: {e}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_examples() {
+        let dir = examples_dir();
+        let jobs = find("11".parse().unwrap(), &[], &dir, Some(&dir)).unwrap();
+        assert_eq!(paths(&jobs), ["day11-example.txt", "day11-example2.txt"]);
+        assert_eq!(
+            jobs[0].label,
+            format!("day 11 (reactor) {}", dir.join("day11-example.txt").display())
+        );
+
+        let jobs = find(Selection::All, &[], &dir, Some(&dir)).unwrap();
+        assert_eq!(jobs.len(), 13);
+        assert!(jobs.iter().all(|job| job.path.is_ok()));
+    }
+
+    #[test]
+    fn missing_input_is_named() {
+        let dir = Path::new("no-such-dir");
+        let jobs = find("3".parse().unwrap(), &[], dir, None).unwrap();
+        assert_eq!(jobs[0].label, "day 03 (lobby)");
+        let e = jobs[0].path.as_ref().unwrap_err().to_string();
+        assert!(
+            e.starts_with(&format!("{} not found", dir.join("day03.txt").display())),
+            "{e}"
+        );
+        assert!(e.contains(INPUTS_VAR), "{e}");
+    }
+
+    #[test]
+    fn named_inputs_come_first() {
+        let dir = Path::new("no-such-dir");
+        let inputs = [PathBuf::from("a"), PathBuf::from("b")];
+        let jobs = find(Selection::All, &inputs, dir, None).unwrap();
+        assert_eq!(jobs.len(), 24);
+        assert_eq!(jobs[13].path.as_ref().unwrap(), &Path::new("b").join("day02.txt"));
+
+        let stdin = [PathBuf::from(common::STDIN)];
+        assert!(find(Selection::All, &stdin, dir, None).is_err());
+        let jobs = find("5".parse().unwrap(), &stdin, dir, None).unwrap();
+        assert_eq!(jobs[0].label, "day 05 (cafeteria)");
+    }
+}
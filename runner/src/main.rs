@@ -0,0 +1,276 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand};
+use common::{Phase, Rng, Timer};
+use eyre::{Result, WrapErr, eyre};
+
+mod days;
+mod inputs;
+mod report;
+mod verify;
+
+use days::{Day, Selection};
+use inputs::Job;
+use report::Output;
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code 2025 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves one day, or every day with `all`.
+    Run {
+        /// Day number (1-12), package name or `all`.
+        day: Selection,
+        /// Only print this part.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Report the time spent reading, parsing and solving each part.
+        #[arg(long)]
+        time: bool,
+        #[arg(long, value_enum, default_value_t = Output::Text)]
+        output: Output,
+        /// Input files, or for `all` directories holding `dayNN.txt` files.
+        ///
+        /// Reads standard input for `-`, and each day's `dayNN.txt` in the inputs
+        /// directory if there are none.
+        inputs: Vec<PathBuf>,
+        #[command(flatten)]
+        discovery: Discovery,
+    },
+    /// Solves days repeatedly and reports the spread of each phase's time.
+    Bench {
+        /// Day number (1-12), package name or `all`.
+        day: Selection,
+        /// Number of times to solve each day.
+        #[arg(short = 'n', long, default_value_t = 10)]
+        #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+        /// Input files, or for `all` directories holding `dayNN.txt` files.
+        ///
+        /// Reads standard input for `-`, and each day's `dayNN.txt` in the inputs
+        /// directory if there are none.
+        inputs: Vec<PathBuf>,
+        #[command(flatten)]
+        discovery: Discovery,
+    },
+    /// Writes random puzzle inputs, reproducible from their seed.
+    Generate {
+        /// Day number (1-12), package name or `all`.
+        day: Selection,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Roughly how many lines or items to generate; what counts depends on the day.
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+        /// Directory to write `dayNN.txt` files to, instead of standard output.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Solves generated inputs with each day's solver and its slow reference
+    /// implementation, stopping at the first input where they disagree.
+    Check {
+        /// Day number (1-12), package name or `all`.
+        day: Selection,
+        /// Number of inputs to try, generated from seeds counting up from 0.
+        #[arg(long, default_value_t = 100)]
+        seeds: u64,
+        /// Roughly how many lines or items to generate; the references are slow, so
+        /// keep it small.
+        #[arg(long, default_value_t = 10)]
+        size: usize,
+    },
+    /// Checks the answers recorded in a manifest file.
+    Verify {
+        /// Manifest with one `<day> <input> <part 1> [<part 2>]` entry per line.
+        #[arg(long, default_value = "answers.txt")]
+        manifest: PathBuf,
+    },
+}
+
+/// Where to look for inputs when none are named.
+#[derive(Args)]
+struct Discovery {
+    /// Directory holding `dayNN.txt` puzzle inputs.
+    #[arg(long, env = inputs::INPUTS_VAR, default_value = "inputs")]
+    inputs_dir: PathBuf,
+    /// Solve every `examples/dayNN-*.txt` file instead.
+    #[arg(long, conflicts_with = "inputs")]
+    examples: bool,
+}
+
+impl Discovery {
+    fn find(&self, day: Selection, inputs: &[PathBuf]) -> Result<Vec<Job>> {
+        let examples = self.examples.then_some(Path::new(inputs::EXAMPLES_DIR));
+        inputs::find(day, inputs, &self.inputs_dir, examples)
+    }
+}
+
+fn solve(day: &Day, input: &Path, timer: &mut Timer) -> Result<common::Answers> {
+    let fname = input.display().to_string();
+    let body = timer.time(Phase::Read, || common::read_input(&fname))?;
+    (day.run)(common::display_name(&fname), &body, timer)
+}
+
+fn run_day(
+    day: &Day,
+    label: &str,
+    input: &Path,
+    part: Option<u8>,
+    time: bool,
+    output: Output,
+) -> Result<()> {
+    let mut timer = Timer::default();
+    let result = solve(day, input, &mut timer).and_then(|answers| {
+        if part == Some(2) && answers.part2.is_none() {
+            Err(eyre!("there is no part 2"))
+        } else {
+            Ok(answers)
+        }
+    });
+    if output == Output::Json {
+        println!("{}", report::json(day, input, part, &result, &timer));
+        return result.map(|_| ());
+    }
+    let answers = result?;
+    if part != Some(2) {
+        println!("{label} part 1: {}", answers.part1);
+    }
+    if let Some(answer) = answers.part2.filter(|_| part != Some(1)) {
+        println!("{label} part 2: {answer}");
+    }
+    for warning in answers.warnings {
+        eprintln!("{label} warning: {warning}");
+    }
+    if time {
+        let phases = Phase::ALL
+            .iter()
+            .filter_map(|&phase| Some(format!("{phase} {:.2?}", timer.total(phase)?)))
+            .collect::<Vec<_>>();
+        println!("{label} time: {}", phases.join(", "));
+    }
+    Ok(())
+}
+
+fn bench_day(day: &Day, label: &str, input: &Path, runs: u32) -> Result<()> {
+    if input.as_os_str() == common::STDIN {
+        return Err(eyre!("bench needs an input file rather than standard input"));
+    }
+    let mut timer = Timer::default();
+    for _ in 0..runs {
+        solve(day, input, &mut timer)?;
+    }
+    println!("{label} over {runs} runs:");
+    for phase in Phase::ALL {
+        if let Some(stats) = timer.stats(phase) {
+            println!(
+                "  {phase:<6}  min {:>10.2?}  median {:>10.2?}  max {:>10.2?}",
+                stats.min, stats.median, stats.max
+            );
+        }
+    }
+    Ok(())
+}
+
+fn generate(day: Selection, seed: u64, size: usize, out: Option<&Path>) -> Result<()> {
+    if day == Selection::All && out.is_none() {
+        return Err(eyre!("all: needs --out to write one file per day"));
+    }
+    for d in day.days() {
+        let body = (d.generate)(&mut Rng::new(seed), size);
+        match out {
+            Some(dir) => {
+                let path = dir.join(format!("day{:02}.txt", d.number));
+                std::fs::write(&path, body)
+                    .wrap_err_with(|| format!("{}: write failed", path.display()))?;
+            }
+            None => print!("{body}"),
+        }
+    }
+    Ok(())
+}
+
+fn check_day(day: &Day, seeds: u64, size: usize) -> Result<()> {
+    let mut compared = 0;
+    for seed in 0..seeds {
+        let body = (day.generate)(&mut Rng::new(seed), size);
+        let number = day.number;
+        compared +=
+            (day.compare)(&format!("seed {seed}"), &body).wrap_err_with(|| {
+                format!("reproduce with: generate {number} --seed {seed} --size {size}")
+            })?;
+    }
+    if compared == 0 {
+        println!("{day}: {seeds} inputs, all too large for the reference");
+    } else {
+        println!("{day}: {seeds} inputs, {compared} answers agree with the reference");
+    }
+    Ok(())
+}
+
+/// Calls `f` with each of `jobs`' days, labels and input files, reporting errors as
+/// it goes.
+fn for_each_job(
+    jobs: Result<Vec<Job>>,
+    mut f: impl FnMut(&Day, &str, &Path) -> Result<()>,
+) -> ExitCode {
+    let jobs = match jobs {
+        Ok(jobs) => jobs,
+        Err(e) => return report_error(Err(e)),
+    };
+    let mut failed = false;
+    for job in jobs {
+        if let Err(e) = job.path.and_then(|path| f(job.day, &job.label, &path)) {
+            eprintln!("{}: {e:?}", job.label);
+            failed = true;
+        }
+    }
+    if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
+
+fn report_error(result: Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run { day, part, time, output, inputs, discovery } => {
+            for_each_job(discovery.find(day, &inputs), |d, label, path| {
+                run_day(d, label, path, part, time, output)
+            })
+        }
+        Command::Bench { day, runs, inputs, discovery } => {
+            for_each_job(discovery.find(day, &inputs), |d, label, path| {
+                bench_day(d, label, path, runs)
+            })
+        }
+        Command::Generate { day, seed, size, out } => {
+            report_error(generate(day, seed, size, out.as_deref()))
+        }
+        Command::Check { day, seeds, size } => {
+            let mut failed = false;
+            for d in day.days() {
+                if let Err(e) = check_day(d, seeds, size) {
+                    eprintln!("{d}: {e:?}");
+                    failed = true;
+                }
+            }
+            if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+        }
+        Command::Verify { manifest } => {
+            report_error(verify::verify_manifest(&manifest))
+        }
+    }
+}
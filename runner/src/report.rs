@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+use common::{Answer, Answers, Phase, Timer};
+use eyre::Result;
+use serde_json::{Map, Value, json};
+
+use crate::days::Day;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    /// Labelled answers, one per line.
+    Text,
+    /// One JSON object per day and input.
+    Json,
+}
+
+fn answer_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Int(n) => i64::try_from(*n)
+            .map(Value::from)
+            .or_else(|_| u64::try_from(*n).map(Value::from))
+            .unwrap_or_else(|_| Value::from(n.to_string())),
+        Answer::Text(s) => Value::from(s.as_str()),
+    }
+}
+
+fn timings_json(timer: &Timer) -> Value {
+    let timings = Phase::ALL
+        .iter()
+        .map(|phase| {
+            let key = phase.to_string().replace(' ', "");
+            let secs = timer.total(*phase).map(|d| d.as_secs_f64());
+            (key, json!(secs))
+        })
+        .collect::<Map<_, _>>();
+    Value::Object(timings)
+}
+
+/// Describes one day's run on one input, with `part` limiting which answers appear.
+pub fn json(
+    day: &Day,
+    input: &Path,
+    part: Option<u8>,
+    result: &Result<Answers>,
+    timer: &Timer,
+) -> Value {
+    let (part1, part2, warnings, error) = match result {
+        Ok(answers) => (
+            (part != Some(2)).then(|| answer_json(&answers.part1)),
+            answers.part2.as_ref().filter(|_| part != Some(1)).map(answer_json),
+            answers.warnings.clone(),
+            None,
+        ),
+        Err(e) => (None, None, Vec::new(), Some(format!("{e:#}"))),
+    };
+    json!({
+        "day": day.number,
+        "name": day.name,
+        "input": common::display_name(&input.display().to_string()),
+        "part1": part1,
+        "part2": part2,
+        "timings": timings_json(timer),
+        "warnings": warnings,
+        "error": error,
+    })
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use common::Timer;
+use eyre::{Result, eyre};
+use nom::bytes::complete::take_till1;
+use nom::character::complete::{space0, space1};
+use nom::combinator::{all_consuming, map_res, opt};
+use nom::sequence::{preceded, terminated};
+use nom::{IResult, Parser};
+
+use crate::days::{DAYS, Day, Selection};
+
+/// One line of the answers manifest: `<day> <input> <part 1> [<part 2>]`.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub day: &'static Day,
+    pub input: PathBuf,
+    pub part1: String,
+    pub part2: Option<String>,
+}
+
+fn parse_token(input: &str) -> IResult<&str, &str> {
+    take_till1(|c: char| c.is_whitespace()).parse(input)
+}
+
+fn parse_day(input: &str) -> IResult<&str, &'static Day> {
+    map_res(parse_token, |s: &str| match s.parse::<Selection>() {
+        Ok(Selection::One(i)) => Ok(&DAYS[i]),
+        _ => Err(()),
+    })
+    .parse(input)
+}
+
+fn parse_entry(input: &str) -> IResult<&str, (&'static Day, &str, &str, Option<&str>)> {
+    all_consuming(preceded(
+        space0,
+        terminated(
+            (
+                terminated(parse_day, space1),
+                terminated(parse_token, space1),
+                parse_token,
+                opt(preceded(space1, parse_token)),
+            ),
+            space0,
+        ),
+    ))
+    .parse(input)
+}
+
+/// Parses the manifest, resolving input paths relative to the manifest's directory.
+///
+/// Blank lines and lines starting with `#` are skipped.
+pub fn parse_manifest(fname: &str, body: &str) -> Result<Vec<Entry>> {
+    let base = Path::new(fname).parent().unwrap_or(Path::new(""));
+    body.lines()
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|line| {
+            let (day, input, part1, part2) =
+                common::finish(fname, body, parse_entry(line))?;
+            Ok(Entry {
+                day,
+                input: base.join(input),
+                part1: part1.to_string(),
+                part2: part2.map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail(String),
+    Missing(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail(why) => write!(f, "FAIL ({why})"),
+            Self::Missing(why) => write!(f, "MISSING ({why})"),
+        }
+    }
+}
+
+fn compare(expected: Option<&str>, actual: Option<String>) -> Outcome {
+    match (expected, actual) {
+        (Some(e), Some(a)) if e == a => Outcome::Pass,
+        (Some(e), Some(a)) => Outcome::Fail(format!("expected {e}, got {a}")),
+        (Some(_), None) => Outcome::Missing("no answer produced".to_string()),
+        (None, Some(a)) => Outcome::Missing(format!("no answer recorded, got {a}")),
+        (None, None) => Outcome::Pass,
+    }
+}
+
+/// Solves the entry's input and compares both parts against the recorded answers.
+pub fn verify(entry: &Entry) -> [Outcome; 2] {
+    let fname = entry.input.display().to_string();
+    if !entry.input.exists() {
+        let missing = Outcome::Missing(format!("{fname} not found"));
+        return [missing.clone(), missing];
+    }
+    let answers = common::read_input(&fname)
+        .and_then(|body| (entry.day.run)(&fname, &body, &mut Timer::default()));
+    match answers {
+        Ok(answers) => [
+            compare(Some(&entry.part1), Some(answers.part1.to_string())),
+            compare(entry.part2.as_deref(), answers.part2.map(|a| a.to_string())),
+        ],
+        Err(e) => {
+            let failed = Outcome::Fail(format!("{e:#}"));
+            [failed.clone(), failed]
+        }
+    }
+}
+
+/// Verifies every entry in the manifest, printing one line per part.
+///
+/// Fails if any part did not pass.
+pub fn verify_manifest(manifest: &Path) -> Result<()> {
+    let fname = manifest.display().to_string();
+    let body = common::read_input(&fname)?;
+    let entries = parse_manifest(&fname, &body)?;
+    let mut passes = 0usize;
+    let mut failures = 0usize;
+    for entry in &entries {
+        let input = entry.input.display();
+        for (part, outcome) in verify(entry).iter().enumerate() {
+            if part == 1 && entry.part2.is_none() && *outcome == Outcome::Pass {
+                continue;
+            }
+            println!("{} {input} part {}: {outcome}", entry.day, part + 1);
+            if *outcome == Outcome::Pass {
+                passes += 1;
+            } else {
+                failures += 1;
+            }
+        }
+    }
+    println!("{passes} passed, {failures} failed or missing");
+    if failures == 0 {
+        Ok(())
+    } else {
+        Err(eyre!("{failures} of the recorded answers did not pass"))
+    }
+}